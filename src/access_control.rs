@@ -0,0 +1,266 @@
+//! ----------------------------------------------
+//! DOCUMENT DETAILS -----------------------------
+//!
+//! filename:access_control.rs
+//! description:Bell-LaPadula checks over classification labels
+//! usages:[config.rs][layer_stack.rs]
+//!
+//! ----------------------------------------------
+//! FEATURE NOTES --------------------------------
+//!
+//! feature_name:async
+//! deps:[tokio][async_trait]
+//! scope:[struct AccessChecker]
+//! corpus:true
+//!
+//! feature_name:logging
+//! deps:[tracing]
+//! scope:[fn AccessChecker::verdict]
+//! corpus:false
+//!
+//! feature_name:std
+//! deps:[std]
+//! scope:[struct AccessChecker]
+//! corpus:false
+//!
+//! ----------------------------------------------
+//! CORPUS FEATURES ------------------------------
+//!
+#![cfg(feature = "async")]
+#![cfg(feature = "std")]
+
+//! Mandatory access control in the Bell-LaPadula model.
+//!
+//! - **No read up** (simple security property): a principal may read an
+//!   object only if its [`Clearance`] dominates the object's [`Label`].
+//! - **No write down** (*-property): a subject that has read data at some
+//!   label may only write to objects whose label dominates it, so secrets
+//!   cannot leak into less protected containers.
+
+use std::collections::BTreeSet;
+
+use zeroize::Zeroize;
+
+#[cfg(feature = "logging")]
+use tracing::warn;
+
+use crate::{
+    classification::{Clearance, Compartment, Label, Level},
+    classified_data::ClassifiedData,
+    config::AccessControlConfig,
+    errors::AccessError,
+};
+
+/// Enforces no-read-up and no-write-down over [`Label`]s.
+///
+/// # Example
+/// ```
+/// use classified::access_control::AccessChecker;
+/// use classified::classification::{Classification, Clearance};
+/// use classified::classified_data::ClassifiedData;
+///
+/// let checker = AccessChecker::new();
+/// let record = ClassifiedData::new(b"iban".to_vec()).with_compartments(["FINANCE"]);
+///
+/// let analyst = Clearance::new(Classification::TopSecret);
+/// assert!(checker.expose(&analyst, &record).is_err());
+///
+/// let auditor = Clearance::new(Classification::Secret).with("FINANCE");
+/// assert_eq!(checker.expose(&auditor, &record).unwrap(), b"iban");
+/// ```
+#[derive(Debug, Clone)]
+pub struct AccessChecker {
+    enforce: bool,
+    allow_write_down: bool,
+    known: BTreeSet<Compartment>,
+}
+
+impl Default for AccessChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccessChecker {
+    /// A strict checker that accepts any compartment name.
+    pub fn new() -> Self {
+        Self {
+            enforce: true,
+            allow_write_down: false,
+            known: BTreeSet::new(),
+        }
+    }
+
+    /// Build a checker from the `[security.access_control]` configuration.
+    pub fn from_config(config: &AccessControlConfig) -> Self {
+        Self::new()
+            .enforce(config.enforce)
+            .allow_write_down(config.allow_write_down)
+            .known_compartments(config.compartments.iter().cloned())
+    }
+
+    /// When `false`, violations are logged but allowed.
+    pub fn enforce(mut self, enforce: bool) -> Self {
+        self.enforce = enforce;
+        self
+    }
+
+    /// Relax the *-property, for pipelines trusted to sanitise what they write.
+    pub fn allow_write_down(mut self, allow: bool) -> Self {
+        self.allow_write_down = allow;
+        self
+    }
+
+    /// Reject labels that name any compartment outside `compartments`.
+    pub fn known_compartments(mut self, compartments: impl IntoIterator<Item = Compartment>) -> Self {
+        self.known.extend(compartments);
+        self
+    }
+
+    /// No read up: `clearance` must dominate `object`.
+    pub fn check_read(&self, clearance: &Clearance, object: &Label) -> Result<(), AccessError> {
+        let result = self.validate(object).and_then(|()| {
+            if clearance.label().dominates(object) {
+                Ok(())
+            } else {
+                Err(AccessError::ReadUp {
+                    clearance: clearance.label().to_string(),
+                    object: object.to_string(),
+                })
+            }
+        });
+        self.verdict(result)
+    }
+
+    /// A subject with no clearance at all may not read anything.
+    pub fn check_read_uncleared(&self, object: &Label) -> Result<(), AccessError> {
+        self.verdict(Err(AccessError::NoClearance {
+            object: object.to_string(),
+        }))
+    }
+
+    /// No write down: a subject that has read data labelled `subject` may
+    /// only write to `object` if `object` dominates `subject`.
+    pub fn check_write(&self, subject: &Label, object: &Label) -> Result<(), AccessError> {
+        let result = self.validate(object).and_then(|()| {
+            if self.allow_write_down || object.dominates(subject) {
+                Ok(())
+            } else {
+                Err(AccessError::WriteDown {
+                    subject: subject.to_string(),
+                    object: object.to_string(),
+                })
+            }
+        });
+        self.verdict(result)
+    }
+
+    /// Expose `data` to a principal with `clearance`, if the read is allowed.
+    pub fn expose<'a, T, L>(
+        &self,
+        clearance: &Clearance,
+        data: &'a ClassifiedData<T, L>,
+    ) -> Result<&'a T, AccessError>
+    where
+        T: Zeroize,
+        L: Level,
+    {
+        self.check_read(clearance, &data.label())?;
+        Ok(data.expose())
+    }
+
+    fn validate(&self, label: &Label) -> Result<(), AccessError> {
+        if self.known.is_empty() {
+            return Ok(());
+        }
+        match label.compartments.iter().find(|c| !self.known.contains(c)) {
+            Some(unknown) => Err(AccessError::UnknownCompartment(unknown.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    fn verdict(&self, result: Result<(), AccessError>) -> Result<(), AccessError> {
+        match result {
+            Err(_e) if !self.enforce => {
+                #[cfg(feature = "logging")]
+                warn!(target: "security_audit", error = %_e, "Access control violation allowed (audit only)");
+                Ok(())
+            }
+            other => other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classification::Classification;
+
+    fn label(level: Classification, compartments: &[&str]) -> Label {
+        compartments.iter().fold(Label::new(level), |l, c| l.with(*c))
+    }
+
+    #[test]
+    fn no_read_up() {
+        let checker = AccessChecker::new();
+        let clearance = Clearance::new(Classification::Secret).with("PII");
+
+        assert!(checker.check_read(&clearance, &label(Classification::Confidential, &["PII"])).is_ok());
+        assert!(matches!(
+            checker.check_read(&clearance, &label(Classification::TopSecret, &[])),
+            Err(AccessError::ReadUp { .. })
+        ));
+        assert!(checker.check_read(&clearance, &label(Classification::Public, &["EXPORT"])).is_err());
+    }
+
+    #[test]
+    fn no_write_down() {
+        let checker = AccessChecker::new();
+        let subject = label(Classification::Secret, &["FINANCE"]);
+
+        assert!(checker.check_write(&subject, &label(Classification::TopSecret, &["FINANCE"])).is_ok());
+        assert!(matches!(
+            checker.check_write(&subject, &label(Classification::Secret, &[])),
+            Err(AccessError::WriteDown { .. })
+        ));
+        assert!(checker
+            .clone()
+            .allow_write_down(true)
+            .check_write(&subject, &label(Classification::Public, &[]))
+            .is_ok());
+    }
+
+    #[test]
+    fn unknown_compartments_are_rejected() {
+        let checker = AccessChecker::new().known_compartments([Compartment::new("PII")]);
+        let clearance = Clearance::new(Classification::TopSecret).with("PII").with("EXPROT");
+
+        assert_eq!(
+            checker.check_read(&clearance, &label(Classification::Public, &["EXPROT"])),
+            Err(AccessError::UnknownCompartment("EXPROT".into()))
+        );
+    }
+
+    #[test]
+    fn audit_only_mode_allows_violations() {
+        let checker = AccessChecker::new().enforce(false);
+        let clearance = Clearance::new(Classification::Public);
+        assert!(checker.check_read(&clearance, &label(Classification::TopSecret, &[])).is_ok());
+    }
+
+    #[test]
+    fn builds_from_config() {
+        let config: AccessControlConfig = toml::from_str(
+            r#"
+            allow_write_down = true
+            compartments = ["pii"]
+            "#,
+        )
+        .unwrap();
+        let checker = AccessChecker::from_config(&config);
+
+        assert!(checker.enforce);
+        assert!(checker.allow_write_down);
+        assert!(checker.known.contains(&Compartment::new("PII")));
+    }
+}
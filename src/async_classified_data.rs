@@ -46,7 +46,7 @@
 //!
 //! This crate provides a `AsyncClassifiedData<T>` type that combines:
 //! - [`SecretBox`] from `secrecy` for memory-safe secret handling
-//! - `tokio::Mutex` for async-safe access
+//! - `tokio::RwLock` for async-safe access with many concurrent readers
 //! - `Zeroize` and `subtle` for secure memory wiping and constant-time comparisons
 //!
 //! Ideal for storing passwords, tokens, keys, or other sensitive in-memory data
@@ -56,7 +56,8 @@
 //! - Cloneable with shallow `Arc` semantics
 //! - Zeroizes memory when dropped
 //! - Redacted `Debug` output
-//! - Optional constant-time comparison (`ct_eq`)
+//! - Constant-time comparison without blocking the runtime (`ct_eq_async`)
+//! - Change notifications via `subscribe`, carrying only a version number
 //!
 //! ## Example
 //! ```rust
@@ -65,31 +66,33 @@
 //! # #[tokio::main]
 //! # async fn main() {
 //! let secret = AsyncClassifiedData::new("top_secret".to_string());
-//! assert_eq!(secret.expose().await, "top_secret");
+//! assert_eq!(secret.with(|s| s.len()).await, 10);
 //!
 //! secret.update(|v| *v = "updated".to_string()).await;
-//! assert_eq!(secret.expose().await, "updated");
+//! assert_eq!(*secret.expose().await, "updated");
 //! # }
 //! ```
 
-use std::{fmt, sync::Arc};
+use std::{fmt, ops::Deref, sync::Arc};
 use secrecy::{ExposeSecret, ExposeSecretMut, SecretBox};
 use zeroize::Zeroize;
-use tokio::sync::Mutex;
+use tokio::sync::{watch, RwLock, RwLockReadGuard};
+use tokio_stream::wrappers::WatchStream;
 
 /// A wrapper for managing sensitive data in async environments with secure memory handling.
 ///
-/// This type encapsulates a [`SecretBox<T>`] inside a `tokio::Mutex`, allowing concurrent
-/// async-safe access while preserving confidentiality guarantees.
+/// This type encapsulates a [`SecretBox<T>`] inside a `tokio::RwLock`. Any number of
+/// readers can borrow the value at once; writers get exclusive access.
 ///
 /// # Security Guarantees
 /// - Underlying data is allocated on the heap and zeroized on drop
-/// - Memory-safe interior mutability via [`Mutex`]
-/// - Constant-time equality support (via [`subtle::ConstantTimeEq`])
+/// - Memory-safe interior mutability via [`RwLock`]
+/// - Reads borrow the value in place; no unzeroized copies are handed out
+/// - Constant-time equality support (via [`AsyncClassifiedData::ct_eq_async`])
 /// - Redacted debug output
-#[derive(Clone)]
 pub struct AsyncClassifiedData<T: Zeroize> {
-    inner: Arc<Mutex<SecretBox<T>>>,
+    inner: Arc<RwLock<SecretBox<T>>>,
+    version: Arc<watch::Sender<u64>>,
 }
 
 impl<T: Zeroize> AsyncClassifiedData<T> {
@@ -99,22 +102,30 @@ impl<T: Zeroize> AsyncClassifiedData<T> {
     /// * `data` - The sensitive value to protect (must implement `Zeroize`)
     pub fn new(data: T) -> Self {
         Self {
-            inner: Arc::new(Mutex::new(SecretBox::new(Box::new(data)))),
+            inner: Arc::new(RwLock::new(SecretBox::new(Box::new(data)))),
+            version: Arc::new(watch::Sender::new(0)),
         }
     }
 
-    /// Asynchronously exposes a **cloned** copy of the sensitive value.
+    /// Asynchronously borrows the sensitive value behind a read guard.
     ///
-    /// This avoids leaking the original reference and ensures the secure box remains intact.
+    /// The guard holds a shared lock and cannot outlive `self`; writers wait
+    /// until every guard is dropped. Keep it short-lived, and never hold it
+    /// across a call to [`update`](Self::update) on the same value.
+    pub async fn expose(&self) -> ExposedGuard<'_, T> {
+        ExposedGuard {
+            guard: self.inner.read().await,
+        }
+    }
+
+    /// Asynchronously runs `f` with a shared borrow of the sensitive value.
     ///
-    /// # Panics
-    /// Panics if `T` does not implement `Clone`.
-    pub async fn expose(&self) -> T
+    /// The borrow ends when `f` returns, so nothing can escape the lock.
+    pub async fn with<R, F>(&self, f: F) -> R
     where
-        T: Clone,
+        F: FnOnce(&T) -> R,
     {
-        let locked = self.inner.lock().await;
-        locked.expose_secret().clone()
+        f(self.inner.read().await.expose_secret())
     }
 
     /// Asynchronously applies a function to **mutate** the sensitive value in place.
@@ -123,12 +134,16 @@ impl<T: Zeroize> AsyncClassifiedData<T> {
     ///
     /// # Arguments
     /// * `f` - A closure that receives a mutable reference to the underlying `T`
+    ///
+    /// Subscribers are notified once the write lock is released.
     pub async fn update<F>(&self, f: F)
     where
         F: FnOnce(&mut T),
     {
-        let mut locked = self.inner.lock().await;
+        let mut locked = self.inner.write().await;
         f(locked.expose_secret_mut());
+        drop(locked);
+        self.version.send_modify(|v| *v += 1);
     }
 
     /// Asynchronously exposes a **mutable reference** to the sensitive value for scoped use.
@@ -139,8 +154,74 @@ impl<T: Zeroize> AsyncClassifiedData<T> {
     where
         F: FnOnce(&mut T),
     {
-        let mut locked = self.inner.lock().await;
-        f(locked.expose_secret_mut());
+        self.update(f).await;
+    }
+
+    /// The number of writes made through [`update`](Self::update) or
+    /// [`expose_mut`](Self::expose_mut) so far.
+    pub fn version(&self) -> u64 {
+        *self.version.borrow()
+    }
+
+    /// Returns a stream that yields the new version after each write.
+    ///
+    /// Notifications carry no secret contents; subscribers read the new value
+    /// with [`with`](Self::with) or [`expose`](Self::expose). Like
+    /// `tokio::sync::watch`, bursts of writes may be coalesced into a single
+    /// notification with the latest version. The stream ends once every
+    /// handle to the value has been dropped.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use classified::async_classified_data::AsyncClassifiedData;
+    /// # use futures::StreamExt;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let password = AsyncClassifiedData::new("hunter2".to_string());
+    /// let mut changes = password.subscribe();
+    ///
+    /// password.update(|v| *v = "correct horse".to_string()).await;
+    ///
+    /// assert_eq!(changes.next().await, Some(1));
+    /// assert_eq!(password.with(|v| v.len()).await, 13);
+    /// # }
+    /// ```
+    pub fn subscribe(&self) -> WatchStream<u64> {
+        WatchStream::from_changes(self.version.subscribe())
+    }
+}
+
+/// Shallow clone: both handles share the same storage, lock and version.
+///
+/// Does not require `T: Clone`, since the secret itself is never copied.
+impl<T: Zeroize> Clone for AsyncClassifiedData<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            version: Arc::clone(&self.version),
+        }
+    }
+}
+
+/// A shared borrow of the value inside an [`AsyncClassifiedData`].
+///
+/// Returned by [`AsyncClassifiedData::expose`]. Holds the read lock until it
+/// is dropped, so it cannot outlive the data it points at.
+pub struct ExposedGuard<'a, T: Zeroize> {
+    guard: RwLockReadGuard<'a, SecretBox<T>>,
+}
+
+impl<T: Zeroize> Deref for ExposedGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.guard.expose_secret()
+    }
+}
+
+impl<T: Zeroize> fmt::Debug for ExposedGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ExposedGuard(REDACTED)")
     }
 }
 
@@ -155,17 +236,54 @@ impl<T: Zeroize> fmt::Debug for AsyncClassifiedData<T> {
 
 use subtle::{Choice, ConstantTimeEq};
 
-impl<T: ConstantTimeEq + Zeroize> ConstantTimeEq for AsyncClassifiedData<T> {
-    /// Performs constant-time equality comparison between two sensitive values.
+use crate::traits::AsyncClassifiedEq;
+
+impl<T: AsRef<[u8]> + Zeroize> AsyncClassifiedData<T> {
+    /// Compares two byte-like sensitive values in constant time without
+    /// blocking the executor.
     ///
-    /// > **Warning**: This uses a blocking executor (`block_on`) and is not recommended in
-    /// production async contexts where deadlocks may occur. Use with caution.
+    /// Both locks are always taken in the same global order (by address), so
+    /// concurrent `a.ct_eq_async(&b)` and `b.ct_eq_async(&a)` cannot deadlock.
+    /// Comparing a value with a clone of itself succeeds without locking twice.
+    pub async fn ct_eq_async(&self, other: &Self) -> Choice {
+        if Arc::ptr_eq(&self.inner, &other.inner) {
+            return Choice::from(1);
+        }
+
+        let self_first = Arc::as_ptr(&self.inner) < Arc::as_ptr(&other.inner);
+        let (first, second) = if self_first {
+            (&self.inner, &other.inner)
+        } else {
+            (&other.inner, &self.inner)
+        };
+
+        let first = first.read().await;
+        let second = second.read().await;
+        let (a, b) = if self_first {
+            (&first, &second)
+        } else {
+            (&second, &first)
+        };
+        a.expose_secret().as_ref().ct_eq(b.expose_secret().as_ref())
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: AsRef<[u8]> + Zeroize + Send + Sync> AsyncClassifiedEq for AsyncClassifiedData<T> {
+    async fn classified_eq_async(&self, rhs: &Self) -> bool {
+        self.ct_eq_async(rhs).await.into()
+    }
+}
+
+#[cfg(feature = "blocking-ct-eq")]
+impl<T: AsRef<[u8]> + Zeroize> ConstantTimeEq for AsyncClassifiedData<T> {
+    /// Blocking wrapper around [`AsyncClassifiedData::ct_eq_async`].
+    ///
+    /// > **Warning**: This drives the comparison with `block_on`. Calling it
+    /// > from inside an async task stalls that worker thread and can deadlock
+    /// > if the lock holder is scheduled on it. Prefer `ct_eq_async`.
     fn ct_eq(&self, other: &Self) -> Choice {
-        futures::executor::block_on(async {
-            let a = self.inner.lock().await;
-            let b = other.inner.lock().await;
-            a.expose_secret().ct_eq(b.expose_secret())
-        })
+        futures::executor::block_on(self.ct_eq_async(other))
     }
 }
 
@@ -175,16 +293,110 @@ mod tests {
     use tokio;
 
     #[tokio::test]
-    async fn test_expose_returns_clone() {
+    async fn test_expose_borrows_in_place() {
         let s = AsyncClassifiedData::new("secret".to_string());
-        assert_eq!(s.expose().await, "secret");
+        let a = s.expose().await;
+        let b = s.expose().await;
+        assert_eq!(*a, "secret");
+        assert_eq!(a.as_ptr(), b.as_ptr());
+    }
+
+    #[tokio::test]
+    async fn test_with_runs_scoped_read() {
+        let s = AsyncClassifiedData::new(vec![1u8, 2, 3]);
+        assert_eq!(s.with(|v| v.iter().sum::<u8>()).await, 6);
+    }
+
+    #[tokio::test]
+    async fn test_update_waits_for_readers() {
+        let s = AsyncClassifiedData::new(1u32);
+        let guard = s.expose().await;
+
+        let writer = {
+            let s = s.clone();
+            tokio::spawn(async move { s.update(|v| *v = 2).await })
+        };
+        tokio::task::yield_now().await;
+        assert_eq!(*guard, 1);
+        drop(guard);
+
+        writer.await.unwrap();
+        assert_eq!(*s.expose().await, 2);
     }
 
     #[tokio::test]
     async fn test_update_changes_value() {
         let s = AsyncClassifiedData::new("abc".to_string());
         s.update(|v| *v = "xyz".to_string()).await;
-        assert_eq!(s.expose().await, "xyz");
+        assert_eq!(*s.expose().await, "xyz");
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_see_new_versions() {
+        use futures::StreamExt;
+
+        let s = AsyncClassifiedData::new(vec![0u8; 4]);
+        let mut changes = s.subscribe();
+
+        s.update(|v| v[0] = 1).await;
+        assert_eq!(changes.next().await, Some(1));
+
+        s.clone().expose_mut(|v| v[0] = 2).await;
+        assert_eq!(changes.next().await, Some(2));
+        assert_eq!(s.version(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_subscription_ends_when_value_dropped() {
+        use futures::StreamExt;
+
+        let s = AsyncClassifiedData::new(1u8);
+        let mut changes = s.subscribe();
+        drop(s);
+        assert_eq!(changes.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_guard_debug_is_redacted() {
+        let s = AsyncClassifiedData::new("secret".to_string());
+        assert_eq!(format!("{:?}", s.expose().await), "ExposedGuard(REDACTED)");
+    }
+
+    #[tokio::test]
+    async fn test_ct_eq_async_compares_values() {
+        let a = AsyncClassifiedData::new(vec![1u8, 2, 3]);
+        let b = AsyncClassifiedData::new(vec![1u8, 2, 3]);
+        let c = AsyncClassifiedData::new(vec![3u8, 2, 1]);
+
+        assert!(bool::from(a.ct_eq_async(&b).await));
+        assert!(!a.classified_eq_async(&c).await);
+        assert!(a.classified_eq_async(&a.clone()).await);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_crossed_comparisons_do_not_deadlock() {
+        let a = AsyncClassifiedData::new(vec![7u8; 32]);
+        let b = AsyncClassifiedData::new(vec![7u8; 32]);
+
+        let tasks: Vec<_> = (0..64)
+            .map(|i| {
+                let (x, y) = if i % 2 == 0 {
+                    (a.clone(), b.clone())
+                } else {
+                    (b.clone(), a.clone())
+                };
+                tokio::spawn(async move { x.classified_eq_async(&y).await })
+            })
+            .collect();
+
+        let all = async {
+            for task in tasks {
+                assert!(task.await.unwrap());
+            }
+        };
+        tokio::time::timeout(std::time::Duration::from_secs(5), all)
+            .await
+            .expect("crossed comparisons deadlocked");
     }
 
     #[tokio::test]
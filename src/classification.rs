@@ -0,0 +1,310 @@
+//! ----------------------------------------------
+//! DOCUMENT DETAILS -----------------------------
+//!
+//! filename:classification.rs
+//! description:type-state classification levels
+//! usages:[classified_data.rs][traits.rs]
+//!
+//! ----------------------------------------------
+//! FEATURE NOTES --------------------------------
+//!
+//! feature_name:none
+//! deps:[]
+//! scope:[trait Level][trait Below][struct Label][struct Clearance]
+//! corpus:true
+//!
+//! feature_name:std
+//! deps:[serde/std]
+//! scope:[impl Deserialize for Compartment, Label, Clearance]
+//! corpus:false
+//!
+//! ----------------------------------------------
+//! CORPUS FEATURES ------------------------------
+//!
+
+//! Classification levels as zero-sized marker types.
+//!
+//! [`ClassifiedData<T, L>`](crate::classified_data::ClassifiedData) carries
+//! its level in the type, so a function can demand
+//! `ClassifiedData<_, Confidential>` and the compiler rejects anything else.
+//! Levels are totally ordered:
+//!
+//! [`Public`] < [`Confidential`] < [`Secret`] < [`TopSecret`]
+//!
+//! Raising a level is always allowed. Lowering one goes through an audited
+//! `downgrade` call with a justification, and releasing a value as plain
+//! data goes through [`declassify`](crate::declassification).
+//!
+//! At runtime a level is combined with a set of [`Compartment`]s into a
+//! [`Label`]. Labels form a lattice: one label dominates another when its
+//! level is at least as high and it holds every compartment of the other.
+//! A [`Clearance`] is the label a principal is trusted up to.
+
+use core::fmt::{self, Debug};
+use std::collections::BTreeSet;
+
+use serde::Deserialize;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A classification level. Implemented only by the markers in this module.
+pub trait Level: sealed::Sealed + Copy + Debug + Default + Send + Sync + 'static {
+    /// Position in the ordering; higher is more sensitive.
+    const RANK: u8;
+    /// The runtime value of this level.
+    const CLASSIFICATION: Classification;
+    /// Human-readable name, used in audit records.
+    const NAME: &'static str;
+}
+
+/// Implemented for `L: Below<H>` whenever `L` is strictly less sensitive than `H`.
+pub trait Below<H: Level>: Level {}
+
+macro_rules! levels {
+    ($($(#[$meta:meta])* $name:ident = $rank:expr;)*) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+            pub struct $name;
+
+            impl sealed::Sealed for $name {}
+
+            impl Level for $name {
+                const RANK: u8 = $rank;
+                const CLASSIFICATION: Classification = Classification::$name;
+                const NAME: &'static str = stringify!($name);
+            }
+        )*
+    };
+}
+
+levels! {
+    /// Data that may be released without restriction.
+    Public = 0;
+    /// Internal data whose disclosure causes limited harm.
+    Confidential = 1;
+    /// Key material and credentials. The default level.
+    Secret = 2;
+    /// Data whose disclosure causes exceptionally grave harm.
+    TopSecret = 3;
+}
+
+macro_rules! below {
+    ($($low:ident < [$($high:ident),*];)*) => {
+        $($(impl Below<$high> for $low {})*)*
+    };
+}
+
+below! {
+    Public < [Confidential, Secret, TopSecret];
+    Confidential < [Secret, TopSecret];
+    Secret < [TopSecret];
+}
+
+/// A classification level as a runtime value, for labels and configuration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Classification {
+    #[default]
+    Public,
+    Confidential,
+    Secret,
+    TopSecret,
+}
+
+impl fmt::Display for Classification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Classification::Public => "PUBLIC",
+            Classification::Confidential => "CONFIDENTIAL",
+            Classification::Secret => "SECRET",
+            Classification::TopSecret => "TOP SECRET",
+        })
+    }
+}
+
+/// A need-to-know category such as `PII`, `FINANCE` or `EXPORT`.
+///
+/// Names are case-insensitive and stored in upper case.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "std", derive(Deserialize), serde(from = "String"))]
+pub struct Compartment(String);
+
+impl Compartment {
+    pub fn new(name: impl AsRef<str>) -> Self {
+        Self(name.as_ref().trim().to_ascii_uppercase())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Compartment {
+    fn from(name: String) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<&str> for Compartment {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl fmt::Display for Compartment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A security label: a level plus a set of compartments.
+///
+/// # Example
+/// ```
+/// use classified::classification::{Classification, Label};
+///
+/// let pii = Label::new(Classification::Confidential).with("PII");
+/// let finance_pii = Label::new(Classification::Secret).with("PII").with("FINANCE");
+///
+/// assert!(finance_pii.dominates(&pii));
+/// assert!(!pii.dominates(&finance_pii));
+/// assert_eq!(finance_pii.to_string(), "SECRET//FINANCE/PII");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "std", derive(Deserialize))]
+pub struct Label {
+    pub level: Classification,
+    #[cfg_attr(feature = "std", serde(default))]
+    pub compartments: BTreeSet<Compartment>,
+}
+
+impl Label {
+    pub fn new(level: Classification) -> Self {
+        Self {
+            level,
+            compartments: BTreeSet::new(),
+        }
+    }
+
+    /// Add a compartment to the label.
+    pub fn with(mut self, compartment: impl Into<Compartment>) -> Self {
+        self.compartments.insert(compartment.into());
+        self
+    }
+
+    /// Whether this label is at least as sensitive as `other`.
+    pub fn dominates(&self, other: &Label) -> bool {
+        self.level >= other.level && self.compartments.is_superset(&other.compartments)
+    }
+
+    /// The least label that dominates both `self` and `other`.
+    pub fn join(&self, other: &Label) -> Label {
+        Label {
+            level: self.level.max(other.level),
+            compartments: self.compartments.union(&other.compartments).cloned().collect(),
+        }
+    }
+
+    /// The greatest label dominated by both `self` and `other`.
+    pub fn meet(&self, other: &Label) -> Label {
+        Label {
+            level: self.level.min(other.level),
+            compartments: self
+                .compartments
+                .intersection(&other.compartments)
+                .cloned()
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.level)?;
+        for (i, compartment) in self.compartments.iter().enumerate() {
+            f.write_str(if i == 0 { "//" } else { "/" })?;
+            write!(f, "{compartment}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The highest label a principal may read.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "std", derive(Deserialize), serde(transparent))]
+pub struct Clearance(Label);
+
+impl Clearance {
+    pub fn new(level: Classification) -> Self {
+        Self(Label::new(level))
+    }
+
+    /// Grant access to a compartment.
+    pub fn with(self, compartment: impl Into<Compartment>) -> Self {
+        Self(self.0.with(compartment))
+    }
+
+    pub fn label(&self) -> &Label {
+        &self.0
+    }
+}
+
+impl From<Label> for Clearance {
+    fn from(label: Label) -> Self {
+        Self(label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_below<L: Below<H>, H: Level>() {
+        assert!(L::RANK < H::RANK);
+    }
+
+    #[test]
+    fn ranks_follow_ordering() {
+        assert_below::<Public, Confidential>();
+        assert_below::<Public, TopSecret>();
+        assert_below::<Confidential, Secret>();
+        assert_below::<Secret, TopSecret>();
+    }
+
+    #[test]
+    fn names_match_markers() {
+        assert_eq!(Public::NAME, "Public");
+        assert_eq!(TopSecret::NAME, "TopSecret");
+        assert_eq!(Secret::CLASSIFICATION, Classification::Secret);
+    }
+
+    #[test]
+    fn compartments_restrict_dominance() {
+        let secret = Label::new(Classification::Secret);
+        let pii = Label::new(Classification::Confidential).with("pii");
+
+        assert!(!secret.dominates(&pii));
+        assert!(!pii.dominates(&secret));
+        assert_eq!(
+            secret.join(&pii),
+            Label::new(Classification::Secret).with("PII")
+        );
+        assert_eq!(secret.meet(&pii), Label::new(Classification::Confidential));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn labels_deserialize_from_toml() {
+        let label: Label = toml::from_str(
+            r#"
+            level = "top_secret"
+            compartments = ["export", "Finance"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(label.to_string(), "TOP SECRET//EXPORT/FINANCE");
+    }
+}
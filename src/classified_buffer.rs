@@ -0,0 +1,363 @@
+//! ----------------------------------------------
+//! DOCUMENT DETAILS -----------------------------
+//!
+//! filename:classified_buffer.rs
+//! description:growable secret byte buffer that wipes on reallocation
+//! usages:[traits.rs]
+//!
+//! ----------------------------------------------
+//! FEATURE NOTES --------------------------------
+//!
+//! feature_name:async
+//! deps:[tokio][async_trait]
+//! scope:[impl From<ClassifiedData>]
+//! corpus:true
+//!
+//! feature_name:std
+//! deps:[std]
+//! scope:[struct ClassifiedBuffer]
+//! corpus:false
+//!
+//! ----------------------------------------------
+//! CORPUS FEATURES ------------------------------
+//!
+#![cfg(feature = "async")]
+#![cfg(feature = "std")]
+
+//! A growable byte buffer for secrets.
+//!
+//! `Vec<u8>` copies its contents to a new allocation when it grows and frees
+//! the old one as is, leaving a stale copy of the secret on the heap.
+//! [`ClassifiedBuffer`] manages its own growth: it allocates the new block,
+//! copies the bytes across, and zeroizes the old block before freeing it.
+//! Shrinking via [`truncate`](ClassifiedBuffer::truncate) wipes the removed
+//! tail as well.
+//!
+//! Like [`ClassifiedData`], a buffer carries a classification level `L` and a
+//! set of compartments, which survive conversions in both directions.
+
+use std::{collections::BTreeSet, fmt, io, marker::PhantomData};
+
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::Zeroize;
+
+use crate::{
+    classification::{Compartment, Label, Level, Secret},
+    classified_data::ClassifiedData,
+    traits::{ClassifiedEq, ClassifiedStorage, Frame},
+};
+
+/// Smallest capacity allocated on first growth.
+const MIN_CAPACITY: usize = 32;
+
+/// A growable secret byte buffer that never leaves stale copies behind.
+///
+/// The contents are only reachable through scoped borrows
+/// ([`with`](Self::with), [`with_mut`](Self::with_mut)). The level `L`
+/// defaults to [`Secret`].
+///
+/// # Example
+/// ```
+/// use std::io::Write;
+/// use classified::classified_buffer::ClassifiedBuffer;
+///
+/// let mut token = ClassifiedBuffer::new();
+/// token.extend_from_slice(b"Bearer ");
+/// write!(token, "{}", "abc123").unwrap();
+///
+/// assert_eq!(token.with(|t| t.len()), 13);
+/// ```
+#[derive(Default)]
+pub struct ClassifiedBuffer<L: Level = Secret> {
+    buf: Vec<u8>,
+    compartments: BTreeSet<Compartment>,
+    _level: PhantomData<L>,
+}
+
+impl ClassifiedBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a buffer that can hold `capacity` bytes before reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::from_vec(Vec::with_capacity(capacity))
+    }
+}
+
+impl<L: Level> ClassifiedBuffer<L> {
+    /// Create an empty buffer at level `L`.
+    pub fn at_level() -> Self {
+        Self::default()
+    }
+
+    fn from_vec(buf: Vec<u8>) -> Self {
+        Self {
+            buf,
+            compartments: BTreeSet::new(),
+            _level: PhantomData,
+        }
+    }
+
+    /// Restrict the contents to principals cleared for every given compartment.
+    pub fn with_compartments<C>(mut self, compartments: impl IntoIterator<Item = C>) -> Self
+    where
+        C: Into<Compartment>,
+    {
+        self.compartments.extend(compartments.into_iter().map(Into::into));
+        self
+    }
+
+    /// This buffer's security label: its level plus its compartments.
+    pub fn label(&self) -> Label {
+        Label {
+            level: L::CLASSIFICATION,
+            compartments: self.compartments.clone(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Append one byte.
+    pub fn push(&mut self, byte: u8) {
+        self.reserve(1);
+        self.buf.push(byte);
+    }
+
+    /// Append `bytes`.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.reserve(bytes.len());
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// Shorten the buffer to `len` bytes, wiping the removed tail.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.buf.len() {
+            self.buf[len..].zeroize();
+            self.buf.truncate(len);
+        }
+    }
+
+    /// Wipe and remove every byte, keeping the allocation.
+    pub fn clear(&mut self) {
+        self.buf.zeroize();
+    }
+
+    /// Make room for `additional` more bytes.
+    ///
+    /// When the current allocation is too small, the contents are moved to
+    /// a new one and the old allocation is zeroized before it is freed.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self
+            .buf
+            .len()
+            .checked_add(additional)
+            .expect("ClassifiedBuffer capacity overflow");
+        if required <= self.buf.capacity() {
+            return;
+        }
+
+        let capacity = required.max(self.buf.capacity() * 2).max(MIN_CAPACITY);
+        let mut grown = Vec::with_capacity(capacity);
+        grown.extend_from_slice(&self.buf);
+        let mut old = std::mem::replace(&mut self.buf, grown);
+        old.zeroize();
+    }
+
+    /// Borrow the contents for the duration of `f`.
+    pub fn with<R>(&self, f: impl FnOnce(&[u8]) -> R) -> R {
+        f(&self.buf)
+    }
+
+    /// Mutably borrow the contents for the duration of `f`.
+    ///
+    /// The length cannot change through the slice; use the growth methods.
+    pub fn with_mut<R>(&mut self, f: impl FnOnce(&mut [u8]) -> R) -> R {
+        f(&mut self.buf)
+    }
+
+    /// Move the contents into a [`ClassifiedData`] with the same level and
+    /// compartments, without copying.
+    pub fn into_classified(mut self) -> ClassifiedData<Vec<u8>, L> {
+        let compartments = std::mem::take(&mut self.compartments);
+        ClassifiedData::at_level(std::mem::take(&mut self.buf)).with_compartments(compartments)
+    }
+}
+
+impl<L: Level> io::Write for ClassifiedBuffer<L> {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<L: Level> fmt::Write for ClassifiedBuffer<L> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+/// Takes over the vector inside a [`ClassifiedData`] without copying it,
+/// keeping its level and compartments.
+impl<L: Level> From<ClassifiedData<Vec<u8>, L>> for ClassifiedBuffer<L> {
+    fn from(data: ClassifiedData<Vec<u8>, L>) -> Self {
+        let (buf, compartments) = data.into_parts();
+        Self {
+            buf,
+            compartments,
+            _level: PhantomData,
+        }
+    }
+}
+
+/// Build a pipeline frame from an assembled buffer.
+impl<L: Level> From<ClassifiedBuffer<L>> for Frame<L> {
+    fn from(buf: ClassifiedBuffer<L>) -> Self {
+        Frame::new(buf.into_classified())
+    }
+}
+
+impl<L: Level> ClassifiedStorage<Vec<u8>> for ClassifiedBuffer<L> {
+    fn classify(value: Vec<u8>) -> Self {
+        Self::from_vec(value)
+    }
+}
+
+impl<L: Level> ConstantTimeEq for ClassifiedBuffer<L> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.buf.as_slice().ct_eq(other.buf.as_slice())
+    }
+}
+
+impl<L: Level> ClassifiedEq for ClassifiedBuffer<L> {
+    fn classified_eq(&self, rhs: &Self) -> bool {
+        self.ct_eq(rhs).into()
+    }
+}
+
+impl<L: Level> Drop for ClassifiedBuffer<L> {
+    fn drop(&mut self) {
+        self.buf.zeroize();
+    }
+}
+
+impl<L: Level> fmt::Debug for ClassifiedBuffer<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ClassifiedBuffer(<REDACTED>)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write as _;
+
+    #[test]
+    fn grows_and_keeps_contents() {
+        let mut buf = ClassifiedBuffer::new();
+        for i in 0..100u8 {
+            buf.push(i);
+        }
+        buf.extend_from_slice(&[200; 50]);
+
+        assert_eq!(buf.len(), 150);
+        buf.with(|b| {
+            assert_eq!(b[99], 99);
+            assert_eq!(b[149], 200);
+        });
+    }
+
+    #[test]
+    fn reallocation_moves_to_new_block() {
+        let mut buf = ClassifiedBuffer::with_capacity(4);
+        buf.extend_from_slice(b"abcd");
+        let before = buf.with(|b| b.as_ptr());
+
+        buf.push(b'e');
+
+        assert_ne!(buf.with(|b| b.as_ptr()), before);
+        assert!(buf.capacity() >= MIN_CAPACITY);
+        buf.with(|b| assert_eq!(b, b"abcde"));
+    }
+
+    #[test]
+    fn truncate_wipes_tail() {
+        let mut buf = ClassifiedBuffer::new();
+        buf.extend_from_slice(b"secret!!");
+        buf.truncate(6);
+
+        buf.with(|b| assert_eq!(b, b"secret"));
+        // SAFETY: reads the spare capacity that `truncate` just wiped; the
+        // bytes were initialised by `extend_from_slice` above.
+        let tail = unsafe { std::slice::from_raw_parts(buf.buf.as_ptr().add(6), 2) };
+        assert_eq!(tail, &[0, 0]);
+    }
+
+    #[test]
+    fn implements_io_and_fmt_write() {
+        let mut buf = ClassifiedBuffer::new();
+        buf.write_all(b"user:").unwrap();
+        std::fmt::Write::write_fmt(&mut buf, format_args!("{}", 42)).unwrap();
+        buf.with(|b| assert_eq!(b, b"user:42"));
+    }
+
+    #[test]
+    fn converts_to_and_from_classified_data() {
+        let data = ClassifiedData::new(b"key".to_vec());
+        let mut buf = ClassifiedBuffer::from(data);
+        buf.push(b'!');
+
+        let back = buf.into_classified();
+        assert_eq!(back.expose(), b"key!");
+
+        let frame = Frame::from(ClassifiedBuffer::<Secret>::classify(b"payload".to_vec()));
+        assert_eq!(frame.data.expose(), b"payload");
+    }
+
+    #[test]
+    fn round_trip_keeps_level_and_compartments() {
+        use crate::classification::TopSecret;
+
+        let data = ClassifiedData::<_, TopSecret>::at_level(b"key".to_vec()).with_compartments(["PII"]);
+        let label = data.label();
+
+        let mut buf = ClassifiedBuffer::from(data);
+        assert_eq!(buf.label(), label);
+        buf.push(b'!');
+
+        let back: ClassifiedData<_, TopSecret> = buf.into_classified();
+        assert_eq!(back.label(), label);
+        assert_eq!(back.expose(), b"key!");
+
+        let frame = Frame::from(ClassifiedBuffer::<TopSecret>::at_level().with_compartments(["PII"]));
+        assert_eq!(frame.data.label(), label);
+    }
+
+    #[test]
+    fn constant_time_eq_and_redacted_debug() {
+        let mut a = ClassifiedBuffer::new();
+        let mut b = ClassifiedBuffer::new();
+        a.extend_from_slice(b"same");
+        b.extend_from_slice(b"same");
+
+        assert!(a.classified_eq(&b));
+        b.push(b'!');
+        assert!(!a.classified_eq(&b));
+        assert_eq!(format!("{:?}", a), "ClassifiedBuffer(<REDACTED>)");
+    }
+}
@@ -40,17 +40,31 @@
 //! 
 #![cfg_attr(feature = "no-clone", doc = "⚠️ Cloning is disabled unless `no-clone` is unset.")]
 
-use secrecy::{ExposeSecret, SecretBox};
+use std::{collections::BTreeSet, marker::PhantomData};
+
+use secrecy::{ExposeSecret, ExposeSecretMut, SecretBox};
 use zeroize::Zeroize;
 
+use crate::classification::{Below, Compartment, Label, Level, Secret};
+use crate::declassification::Principal;
+use crate::scratch::Scratch;
+use futures::future::BoxFuture;
+
 /// A secure wrapper for sensitive data that ensures memory is zeroed on drop,
 /// and access is tightly controlled through secure methods.
 ///
 /// `ClassifiedData<T>` is designed for managing secret values (keys, tokens, etc.)
 /// that implement [`Zeroize`]. It leverages [`SecretBox`] from the `secrecy` crate
 /// for automatic zeroing, and optionally restricts cloning via a feature flag.
-pub struct ClassifiedData<T: Zeroize> {
+///
+/// The classification level `L` (see [`crate::classification`]) defaults to
+/// [`Secret`] and only changes through [`upgrade`](Self::upgrade) or
+/// [`downgrade`](Self::downgrade). Compartments are attached at runtime
+/// with [`with_compartments`](Self::with_compartments).
+pub struct ClassifiedData<T: Zeroize, L: Level = Secret> {
     data: SecretBox<T>,
+    compartments: BTreeSet<Compartment>,
+    _level: PhantomData<L>,
 }
 
 impl<T: Zeroize> ClassifiedData<T> {
@@ -64,37 +78,280 @@ impl<T: Zeroize> ClassifiedData<T> {
     /// let secret = ClassifiedData::new("my-api-key".to_string());
     /// ```
     pub fn new(data: T) -> Self {
+        Self::at_level(data)
+    }
+}
+
+impl<T: Zeroize, L: Level> ClassifiedData<T, L> {
+    /// Create a new classified value at level `L`.
+    ///
+    /// # Example
+    /// ```
+    /// use classified::{classification::Confidential, classified_data::ClassifiedData};
+    /// let email = ClassifiedData::<_, Confidential>::at_level("a@example.com".to_string());
+    /// ```
+    pub fn at_level(data: T) -> Self {
         ClassifiedData {
             data: SecretBox::new(Box::new(data)),
+            compartments: BTreeSet::new(),
+            _level: PhantomData,
         }
     }
 
-    // /// Expose a reference to the inner value.
-    // ///
-    // /// ⚠️ Use with care. This is a read-only view of sensitive data.
-    // #[must_use = "You must never ignore confidential data"]
-    // pub fn expose(&self) -> &T {
-    //     self.data.expose_secret()
-    // }
-}
+    /// Restrict the value to principals cleared for every given compartment.
+    pub fn with_compartments<C>(mut self, compartments: impl IntoIterator<Item = C>) -> Self
+    where
+        C: Into<Compartment>,
+    {
+        self.compartments.extend(compartments.into_iter().map(Into::into));
+        self
+    }
+
+    /// The compartments attached to this value.
+    pub fn compartments(&self) -> &BTreeSet<Compartment> {
+        &self.compartments
+    }
+
+    /// This value's security label: its level plus its compartments.
+    pub fn label(&self) -> Label {
+        Label {
+            level: L::CLASSIFICATION,
+            compartments: self.compartments.clone(),
+        }
+    }
+
+    /// Expose a reference to the inner value.
+    ///
+    /// ⚠️ Use with care. This is a read-only view of sensitive data.
+    #[must_use = "You must never ignore confidential data"]
+    pub fn expose(&self) -> &T {
+        self.data.expose_secret()
+    }
 
-impl_expose!(ClassifiedData);
+    /// Move the value to a more sensitive level. Always allowed.
+    ///
+    /// # Example
+    /// ```
+    /// use classified::classification::{Confidential, TopSecret};
+    /// use classified::classified_data::ClassifiedData;
+    ///
+    /// let low = ClassifiedData::<_, Confidential>::at_level(vec![1u8]);
+    /// let high: ClassifiedData<_, TopSecret> = low.upgrade();
+    /// ```
+    pub fn upgrade<H: Level>(self) -> ClassifiedData<T, H>
+    where
+        L: Below<H>,
+    {
+        self.relabel()
+    }
+
+    /// Move the value to a less sensitive level on the authority of
+    /// `authorizer`, keeping it classified.
+    ///
+    /// Every call is recorded through the
+    /// [declassification sink](crate::declassification) with the current
+    /// label, the target level and the caller-supplied `justification`. To
+    /// release the value as plain data, use [`declassify`](Self::declassify).
+    ///
+    /// # Panics
+    /// Panics if `justification` is blank.
+    #[track_caller]
+    pub fn downgrade<D>(self, justification: &str, authorizer: &Principal) -> ClassifiedData<T, D>
+    where
+        D: Below<L>,
+    {
+        crate::declassification::audit_downgrade(
+            "ClassifiedData",
+            self.label(),
+            D::NAME,
+            justification,
+            authorizer,
+        );
+        self.relabel()
+    }
+
+    /// The name of this value's classification level.
+    pub fn level(&self) -> &'static str {
+        L::NAME
+    }
+
+    /// Moves the box into a wrapper with a different level, without copying.
+    fn relabel<M: Level>(self) -> ClassifiedData<T, M> {
+        // `Self` implements `Drop`, so the box cannot be moved out directly.
+        let this = std::mem::ManuallyDrop::new(self);
+        // SAFETY: `this` is never used or dropped again, so reading the
+        // fields out transfers sole ownership of them.
+        let (data, compartments) =
+            unsafe { (std::ptr::read(&this.data), std::ptr::read(&this.compartments)) };
+        ClassifiedData {
+            data,
+            compartments,
+            _level: PhantomData,
+        }
+    }
+}
 
-impl<T: Clone + Zeroize> ClassifiedData<T> {
-    /// Clone the inner data, apply a mutation, and discard the result.
+impl<T: Zeroize, L: Level> ClassifiedData<T, L> {
+    /// Mutate the secret in place.
     ///
-    /// This avoids mutating the original data and can be used for
-    /// computations like hashing or transformations in a controlled way.
-    pub async fn update<F>(&self, f: F)
+    /// # Example
+    /// ```
+    /// use classified::classified_data::ClassifiedData;
+    /// let mut counter = ClassifiedData::new(vec![1u8]);
+    /// counter.update(|v| v.push(2));
+    /// assert_eq!(counter.expose(), &[1, 2]);
+    /// ```
+    pub fn update<F>(&mut self, f: F)
     where
         F: FnOnce(&mut T),
     {
-        let mut data = self.data.expose_secret().clone();
-        f(&mut data);
+        f(self.data.expose_secret_mut());
+    }
+
+    /// Derive a new secret from this one without exposing it to the caller.
+    ///
+    /// Intermediates allocated in the [`Scratch`] are zeroized before this
+    /// method returns. The result keeps this value's level and compartments.
+    ///
+    /// # Example
+    /// ```
+    /// use classified::classified_data::ClassifiedData;
+    ///
+    /// let password = ClassifiedData::new(b"hunter2".to_vec());
+    /// let salted = password.map(|pw, scratch| {
+    ///     let buf = scratch.bytes(pw.len() + 4);
+    ///     buf[..4].copy_from_slice(b"salt");
+    ///     buf[4..].copy_from_slice(pw);
+    ///     buf.iter().map(|b| b.wrapping_add(1)).collect::<Vec<u8>>()
+    /// });
+    /// assert_eq!(salted.expose().len(), 11);
+    /// ```
+    pub fn map<U, F>(&self, f: F) -> ClassifiedData<U, L>
+    where
+        U: Zeroize,
+        F: FnOnce(&T, &Scratch) -> U,
+    {
+        let scratch = Scratch::new();
+        self.derive(f(self.expose(), &scratch))
+    }
+
+    /// Fallible [`map`](Self::map). The scratch space is wiped on both paths.
+    pub fn try_map<U, E, F>(&self, f: F) -> core::result::Result<ClassifiedData<U, L>, E>
+    where
+        U: Zeroize,
+        F: FnOnce(&T, &Scratch) -> core::result::Result<U, E>,
+    {
+        let scratch = Scratch::new();
+        f(self.expose(), &scratch).map(|value| self.derive(value))
+    }
+
+    /// Combine two secrets of the same level into a new one.
+    ///
+    /// The result carries the compartments of both inputs.
+    pub fn zip<U, V, F>(&self, other: &ClassifiedData<U, L>, f: F) -> ClassifiedData<V, L>
+    where
+        U: Zeroize,
+        V: Zeroize,
+        F: FnOnce(&T, &U, &Scratch) -> V,
+    {
+        let scratch = Scratch::new();
+        self.derive(f(self.expose(), other.expose(), &scratch))
+            .with_compartments(other.compartments.iter().cloned())
+    }
+
+    /// Async [`map`](Self::map), for derivations that await (e.g. a KMS call).
+    ///
+    /// The closure returns a boxed future borrowing both arguments:
+    ///
+    /// ```
+    /// use classified::classified_data::ClassifiedData;
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let key = ClassifiedData::new(vec![1u8, 2, 3]);
+    /// let doubled = key
+    ///     .map_async(|k, _scratch| Box::pin(async move { k.iter().map(|b| b * 2).collect::<Vec<u8>>() }))
+    ///     .await;
+    /// assert_eq!(doubled.expose(), &[2, 4, 6]);
+    /// # }
+    /// ```
+    pub async fn map_async<U, F>(&self, f: F) -> ClassifiedData<U, L>
+    where
+        T: Sync,
+        U: Zeroize,
+        F: for<'a> FnOnce(&'a T, &'a Scratch) -> BoxFuture<'a, U>,
+    {
+        let scratch = Scratch::new();
+        let value = f(self.expose(), &scratch).await;
+        self.derive(value)
+    }
+
+    /// Wrap a value derived from this one at the same level and compartments.
+    fn derive<U: Zeroize>(&self, value: U) -> ClassifiedData<U, L> {
+        ClassifiedData::at_level(value).with_compartments(self.compartments.iter().cloned())
+    }
+}
+
+impl<T: Zeroize, L: Level> ClassifiedData<T, L> {
+    /// Release the value as plain data on the authority of `authorizer`.
+    ///
+    /// This is the only way to take the value out of the wrapper. The
+    /// release, with this value's label, is recorded through the
+    /// [declassification sink](crate::declassification).
+    ///
+    /// # Panics
+    /// Panics if `reason` is blank.
+    ///
+    /// # Example
+    /// ```
+    /// use classified::classified_data::ClassifiedData;
+    /// use classified::declassification::Principal;
+    ///
+    /// let fingerprint = ClassifiedData::new(b"SHA256:abc".to_vec());
+    /// let published = fingerprint.declassify("publish host key fingerprint", &Principal::new("ops"));
+    /// assert_eq!(published, b"SHA256:abc");
+    /// ```
+    #[track_caller]
+    pub fn declassify(self, reason: &str, authorizer: &Principal) -> T {
+        crate::declassification::audit("ClassifiedData", Some(self.label()), reason, authorizer);
+        self.into_value()
+    }
+
+    /// Move the value out of its box; the vacated heap slot is wiped.
+    pub(crate) fn into_value(self) -> T {
+        self.into_parts().0
+    }
+
+    /// Move the value and its compartments out; the vacated heap slot is wiped.
+    pub(crate) fn into_parts(self) -> (T, BTreeSet<Compartment>) {
+        let this = std::mem::ManuallyDrop::new(self);
+        // SAFETY: `this` is never used or dropped again, so reading the
+        // fields out transfers sole ownership of them.
+        let (data, compartments) =
+            unsafe { (std::ptr::read(&this.data), std::ptr::read(&this.compartments)) };
+        (crate::zeroizing_guard::unbox_secret(data), compartments)
     }
 }
 
-impl<T: AsRef<[u8]> + Zeroize> ClassifiedData<T> {
+#[cfg(unix)]
+use crate::locked_classified_data::{GuardedClassifiedData, LockedClassifiedData};
+
+#[cfg(unix)]
+impl<T: Zeroize, L: Level> ClassifiedData<T, L> {
+    /// Move the secret onto `mlock`ed pages.
+    ///
+    /// The value is moved out of the heap box, whose slot is wiped before it
+    /// is freed.
+    pub fn into_locked(self) -> LockedClassifiedData<T> {
+        LockedClassifiedData::new(self.into_value())
+    }
+
+    /// Move the secret onto locked pages fenced by guard pages and a canary.
+    pub fn into_guarded(self) -> GuardedClassifiedData<T> {
+        GuardedClassifiedData::new(self.into_value())
+    }
+}
+
+impl<T: AsRef<[u8]> + Zeroize, L: Level> ClassifiedData<T, L> {
     /// Check if the inner byte-like data is empty.
     pub fn is_empty(&self) -> bool {
         self.data.expose_secret().as_ref().is_empty()
@@ -105,24 +362,29 @@ use subtle::{ConstantTimeEq, Choice};
 
 /// Implements constant-time equality for `ClassifiedData<Vec<u8>>`,
 /// suitable for comparing secret values without leaking timing info.
-impl ConstantTimeEq for ClassifiedData<Vec<u8>> {
+impl<L: Level> ConstantTimeEq for ClassifiedData<Vec<u8>, L> {
     fn ct_eq(&self, other: &Self) -> Choice {
         self.expose().ct_eq(other.expose())
     }
 }
 
-use crate::impl_expose;
-use crate::traits::ClassifiedEq;
+use crate::traits::{ClassifiedEq, ClassifiedStorage};
+
+impl<T: Zeroize, L: Level> ClassifiedStorage<T> for ClassifiedData<T, L> {
+    fn classify(value: T) -> Self {
+        ClassifiedData::at_level(value)
+    }
+}
 
 /// Provides a semantic wrapper over `ct_eq`, returning a boolean.
-impl ClassifiedEq for ClassifiedData<Vec<u8>> {
+impl<L: Level> ClassifiedEq for ClassifiedData<Vec<u8>, L> {
     fn classified_eq(&self, rhs: &Self) -> bool {
         self.ct_eq(rhs).into()
     }
 }
 
 /// Ensures that the inner secret is zeroized before memory is freed.
-impl<T: Zeroize> Drop for ClassifiedData<T> {
+impl<T: Zeroize, L: Level> Drop for ClassifiedData<T, L> {
     fn drop(&mut self) {
         self.data.zeroize();
     }
@@ -132,23 +394,23 @@ impl<T: Zeroize> Drop for ClassifiedData<T> {
 // use macros for impls
 // use crate::impl_drop;
 // impl_drop!(ClassifiedData);
+crate::impl_ct_ops!(ClassifiedData, ClassifiedData::at_level, L: Level; labels = compartments);
 
 #[cfg(not(feature = "no-clone"))]
 /// Allows cloning of classified data only if the feature `no-clone` is not set.
 ///
 /// ⚠️ Cloning secrets can be dangerous and should only be enabled when necessary.
-impl<T: Clone + Zeroize> Clone for ClassifiedData<T> {
+impl<T: Clone + Zeroize, L: Level> Clone for ClassifiedData<T, L> {
     fn clone(&self) -> Self {
-        ClassifiedData {
-            data: SecretBox::new(Box::new(self.data.expose_secret().clone())),
-        }
+        ClassifiedData::at_level(self.data.expose_secret().clone())
+            .with_compartments(self.compartments.iter().cloned())
     }
 }
 
 #[cfg(feature = "no-clone")]
 /// Prevents cloning of classified data when `no-clone` is enabled,
 /// enforcing strong immutability of secrets.
-impl<T: Clone + Zeroize> Clone for ClassifiedData<T> {
+impl<T: Clone + Zeroize, L: Level> Clone for ClassifiedData<T, L> {
     fn clone(&self) -> Self {
         panic!("Cloning sensitive data is forbidden");
     }
@@ -160,7 +422,7 @@ use std::ops::Deref;
 ///
 /// ⚠️ This should be used carefully, especially with traits that
 /// could expose the inner secret (e.g., `Debug`, `Display`).
-impl<T: Zeroize> Deref for ClassifiedData<T> {
+impl<T: Zeroize, L: Level> Deref for ClassifiedData<T, L> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
@@ -173,7 +435,7 @@ use std::fmt::{Debug, Formatter, Result};
 /// Hides inner secrets from accidental logging or printing.
 ///
 /// Always shows `<redacted>`, regardless of the wrapped value.
-impl<T: Zeroize> Debug for ClassifiedData<T> {
+impl<T: Zeroize, L: Level> Debug for ClassifiedData<T, L> {
     fn fmt(&self, f: &mut Formatter) -> Result {
         write!(f, "ClassifiedData(<redacted>)")
     }
@@ -191,17 +453,189 @@ mod tests {
         assert_eq!(sensitive.expose(), &[1, 2, 3]);
     }
 
-    /// Verifies that `.update()` clones and modifies a temporary copy,
-    /// and the original value remains unchanged.
-    #[tokio::test]
-    async fn update_clones_and_applies_function_but_does_not_mutate_original() {
-        let sensitive = ClassifiedData::new(vec![1, 2, 3]);
+    /// Verifies that `.update()` mutates the secret in place.
+    #[test]
+    fn update_mutates_in_place() {
+        let mut sensitive = ClassifiedData::new(vec![1, 2, 3]);
+        let ptr = sensitive.expose() as *const Vec<i32>;
 
         sensitive.update(|v| {
             v.push(9);
-        }).await;
+        });
 
-        assert_eq!(sensitive.expose(), &[1, 2, 3]);
+        assert_eq!(sensitive.expose(), &[1, 2, 3, 9]);
+        assert_eq!(sensitive.expose() as *const Vec<i32>, ptr);
+    }
+
+    /// Verifies that `map` keeps the label and wipes its scratch space.
+    #[test]
+    fn map_derives_new_secret_and_wipes_scratch() {
+        use std::sync::{atomic::{AtomicBool, Ordering}, Arc};
+
+        struct Flag(Arc<AtomicBool>);
+        impl Zeroize for Flag {
+            fn zeroize(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let wiped = Arc::new(AtomicBool::new(false));
+        let key = ClassifiedData::new(vec![1u8, 2]).with_compartments(["PII"]);
+        let derived = key.map(|k, scratch| {
+            scratch.keep(Flag(Arc::clone(&wiped)));
+            k.len()
+        });
+
+        assert_eq!(*derived.expose(), 2);
+        assert_eq!(derived.label(), key.label());
+        assert!(wiped.load(Ordering::SeqCst));
+    }
+
+    /// Verifies that `try_map` propagates errors.
+    #[test]
+    fn try_map_propagates_errors() {
+        let key = ClassifiedData::new(vec![0u8; 3]);
+        let result: std::result::Result<ClassifiedData<u8>, &str> =
+            key.try_map(|k, _| k.first().copied().filter(|b| *b != 0).ok_or("zero"));
+        assert_eq!(result.unwrap_err(), "zero");
+    }
+
+    /// Verifies that `zip` combines values and compartments.
+    #[test]
+    fn zip_combines_secrets() {
+        let a = ClassifiedData::new(vec![1u8, 2]).with_compartments(["PII"]);
+        let b = ClassifiedData::new(vec![3u8]).with_compartments(["FINANCE"]);
+
+        let joined = a.zip(&b, |x, y, _| [x.as_slice(), y.as_slice()].concat());
+        assert_eq!(joined.expose(), &[1, 2, 3]);
+        assert_eq!(joined.compartments().len(), 2);
+    }
+
+    /// Verifies that `map_async` awaits the derivation.
+    #[tokio::test]
+    async fn map_async_awaits_derivation() {
+        let key = ClassifiedData::new(vec![5u8]);
+        let derived = key
+            .map_async(|k, scratch| {
+                Box::pin(async move {
+                    let buf = scratch.bytes(1);
+                    buf[0] = k[0] + 1;
+                    tokio::task::yield_now().await;
+                    buf[0]
+                })
+            })
+            .await;
+        assert_eq!(*derived.expose(), 6);
+    }
+
+    /// Verifies that moving a secret into guarded storage keeps its value.
+    #[cfg(unix)]
+    #[test]
+    fn into_guarded_moves_value() {
+        let sensitive = ClassifiedData::new(vec![4u8, 5, 6]);
+        let guarded = sensitive.into_guarded();
+        assert_eq!(guarded.expose(), &[4, 5, 6]);
+    }
+
+    /// Verifies that values keep their contents when moving between levels.
+    #[test]
+    fn upgrade_and_downgrade_keep_value() {
+        use crate::classification::{Confidential, Public, TopSecret};
+
+        let low = ClassifiedData::<_, Confidential>::at_level(vec![1u8, 2]);
+        let ptr = low.expose().as_ptr();
+
+        let high: ClassifiedData<_, TopSecret> = low.upgrade();
+        assert_eq!(high.level(), "TopSecret");
+        assert_eq!(high.expose().as_ptr(), ptr);
+
+        let public: ClassifiedData<_, Public> =
+            high.downgrade("published fingerprint", &Principal::new("erin"));
+        assert_eq!(public.level(), "Public");
+        assert_eq!(public.expose(), &[1, 2]);
+    }
+
+    /// Verifies that downgrades are recorded through the declassification sink.
+    #[test]
+    fn downgrade_is_recorded_through_sink() {
+        use crate::classification::{Confidential, TopSecret};
+
+        let sink = crate::declassification::tests::recording_sink();
+        let high = ClassifiedData::<_, TopSecret>::at_level(vec![3u8]).with_compartments(["PII"]);
+
+        let low: ClassifiedData<_, Confidential> =
+            high.downgrade("classified_data downgrade test", &Principal::new("frank"));
+        assert_eq!(low.expose(), &[3]);
+
+        let records = sink.0.lock().unwrap();
+        let record = records
+            .iter()
+            .find(|r| r.reason == "classified_data downgrade test")
+            .unwrap();
+        assert_eq!(record.principal, "frank");
+        assert_eq!(record.label.as_ref().unwrap().to_string(), "TOP SECRET//PII");
+        assert_eq!(record.downgraded_to, Some("Confidential"));
+        assert!(record.location.file().ends_with("classified_data.rs"));
+    }
+
+    /// Verifies that values without a `Default` impl can still be released.
+    #[test]
+    fn declassify_does_not_need_default() {
+        struct NoDefault(Vec<u8>);
+
+        impl Zeroize for NoDefault {
+            fn zeroize(&mut self) {
+                self.0.zeroize();
+            }
+        }
+
+        let secret = ClassifiedData::new(NoDefault(vec![1, 2, 3]));
+        let released = secret.declassify("no default release test", &Principal::new("gina"));
+        assert_eq!(released.0, [1, 2, 3]);
+    }
+
+    /// Verifies that compartments survive relabelling and show up in the label.
+    #[test]
+    fn label_combines_level_and_compartments() {
+        use crate::classification::{Classification, TopSecret};
+
+        let value = ClassifiedData::new(vec![1u8]).with_compartments(["pii", "finance"]);
+        let value: ClassifiedData<_, TopSecret> = value.upgrade();
+        let direct = ClassifiedData::<_, TopSecret>::at_level(vec![1u8]).with_compartments(["FINANCE", "PII"]);
+
+        let expected = Label::new(Classification::TopSecret).with("PII").with("FINANCE");
+        assert_eq!(value.label(), expected);
+        assert_eq!(direct.label(), expected);
+        #[cfg(not(feature = "no-clone"))]
+        assert_eq!(value.clone().label(), expected);
+    }
+
+    /// Verifies that declassification returns the value and records its label.
+    #[test]
+    fn declassify_releases_value_with_audit_record() {
+        let sink = crate::declassification::tests::recording_sink();
+        let secret = ClassifiedData::new(vec![9u8, 9]).with_compartments(["EXPORT"]);
+
+        let public = secret.declassify("classified_data release test", &Principal::new("dave"));
+        assert_eq!(public, vec![9, 9]);
+
+        let records = sink.0.lock().unwrap();
+        let record = records
+            .iter()
+            .find(|r| r.reason == "classified_data release test")
+            .unwrap();
+        assert_eq!(record.principal, "dave");
+        assert_eq!(record.label.as_ref().unwrap().to_string(), "SECRET//EXPORT");
+        assert!(record.location.file().ends_with("classified_data.rs"));
+    }
+
+    /// Verifies that the default level is `Secret`.
+    #[test]
+    fn default_level_is_secret() {
+        fn takes_secret(_: &ClassifiedData<Vec<u8>, Secret>) {}
+        let value = ClassifiedData::new(vec![0u8]);
+        takes_secret(&value);
+        assert_eq!(value.level(), "Secret");
     }
 
     /// Tests equality between two equal secrets using constant-time comparison.
@@ -264,4 +698,41 @@ mod tests {
 
         assert!(DROP_FLAG.with(|f| *f.borrow()), "Drop was not called");
     }
+
+    #[test]
+    fn constant_time_ops_keep_level_and_round_trip() {
+        use crate::{classification::Confidential, constant_time::ConstantTimeOps};
+
+        let mut a = ClassifiedData::<_, Confidential>::at_level(vec![1u8, 2]);
+        let mut b = ClassifiedData::<_, Confidential>::at_level(vec![3u8, 4]);
+        assert!(bool::from(a.ct_lt(&b)));
+
+        a.ct_conditional_swap(&mut b, Choice::from(1));
+        assert_eq!(a.expose(), &[3, 4]);
+        assert_eq!(b.to_hex().expose(), "0102");
+
+        let decoded = ClassifiedData::<Vec<u8>, Confidential>::from_base64("AQI=").unwrap();
+        assert_eq!(decoded.expose(), b.expose());
+        assert_eq!(decoded.level(), "Confidential");
+    }
+
+    #[test]
+    fn constant_time_select_and_swap_keep_compartments() {
+        use crate::constant_time::ConstantTimeOps;
+
+        let mut a = ClassifiedData::new(vec![1u8, 2]).with_compartments(["PII"]);
+        let mut b = ClassifiedData::new(vec![3u8, 4]).with_compartments(["FINANCE"]);
+        let joined = Label::new(crate::classification::Classification::Secret)
+            .with("PII")
+            .with("FINANCE");
+
+        let picked = ClassifiedData::ct_conditional_select(&a, &b, Choice::from(0));
+        assert_eq!(picked.expose(), &[1, 2]);
+        assert_eq!(picked.label(), joined);
+
+        a.ct_conditional_swap(&mut b, Choice::from(1));
+        assert_eq!(b.expose(), &[1, 2]);
+        assert_eq!(a.label(), joined);
+        assert_eq!(b.label(), joined);
+    }
 }
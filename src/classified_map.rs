@@ -47,9 +47,14 @@ use std::{
     collections::HashMap,
     fmt::Debug,
     hash::Hash,
+    marker::PhantomData,
 };
 
-use crate::classified_data::ClassifiedData;
+use crate::{
+    classified_data::ClassifiedData,
+    shielded_classified_data::{Shieldable, ShieldedClassifiedData},
+    traits::ClassifiedStorage,
+};
 use zeroize::Zeroize;
 
 /// A map structure that stores sensitive values using `ClassifiedData<V>`,
@@ -61,15 +66,24 @@ use zeroize::Zeroize;
 /// # Type Parameters
 /// - `K`: The key type. Must implement `Eq + Hash + Clone + Debug`.
 /// - `V`: The value type. Must implement `Zeroize + Clone + Debug`.
+/// - `S`: How each value is stored. Defaults to [`ClassifiedData<V>`]; use
+///   [`ShieldedClassifiedMap`] to keep idle values encrypted in memory.
 #[derive(Debug)]
-pub struct ClassifiedMap<K, V>
+pub struct ClassifiedMap<K, V, S = ClassifiedData<V>>
 where
     K: Eq + Hash + Clone + Debug,
     V: Zeroize + Clone + Debug,
+    S: ClassifiedStorage<V>,
 {
-    inner: HashMap<K, ClassifiedData<V>>,
+    inner: HashMap<K, S>,
+    _marker: PhantomData<V>,
 }
 
+/// A [`ClassifiedMap`] whose values are shielded while idle.
+///
+/// Values are read through [`ShieldedClassifiedData::expose`].
+pub type ShieldedClassifiedMap<K, V> = ClassifiedMap<K, V, ShieldedClassifiedData<V>>;
+
 impl<K, V> ClassifiedMap<K, V>
 where
     K: Eq + Hash + Clone + Debug,
@@ -88,8 +102,43 @@ where
     /// let map: ClassifiedMap<String, SecretValue> = ClassifiedMap::new();
     /// ```
     pub fn new() -> Self {
+        Self::with_storage()
+    }
+}
+
+impl<K, V> ShieldedClassifiedMap<K, V>
+where
+    K: Eq + Hash + Clone + Debug,
+    V: Shieldable + Clone + Debug,
+{
+    /// Creates a new, empty map that shields every stored value.
+    ///
+    /// # Example
+    /// ```
+    /// use classified::classified_map::ShieldedClassifiedMap;
+    ///
+    /// let mut map = ShieldedClassifiedMap::new_shielded();
+    /// map.insert("api_key", b"hunter2".to_vec());
+    ///
+    /// let len = map.get(&"api_key").unwrap().expose(|v| v.len()).unwrap();
+    /// assert_eq!(len, 7);
+    /// ```
+    pub fn new_shielded() -> Self {
+        Self::with_storage()
+    }
+}
+
+impl<K, V, S> ClassifiedMap<K, V, S>
+where
+    K: Eq + Hash + Clone + Debug,
+    V: Zeroize + Clone + Debug,
+    S: ClassifiedStorage<V>,
+{
+    /// Creates a new, empty map using the storage type `S`.
+    pub fn with_storage() -> Self {
         Self {
             inner: HashMap::new(),
+            _marker: PhantomData,
         }
     }
 
@@ -117,15 +166,15 @@ where
     /// map.insert("api_key".to_string(), secret_value);
     /// ```
     pub fn insert(&mut self, key: K, value: V) {
-        self.inner.insert(key, ClassifiedData::new(value));
+        self.inner.insert(key, S::classify(value));
     }
 
     /// Retrieves a reference to a classified value by key.
     ///
     /// # Returns
-    /// - `Some(&ClassifiedData<V>)` if the key exists.
+    /// - `Some(&S)` if the key exists.
     /// - `None` otherwise.
-    pub fn get(&self, key: &K) -> Option<&ClassifiedData<V>> {
+    pub fn get(&self, key: &K) -> Option<&S> {
         self.inner.get(key)
     }
 
@@ -145,8 +194,8 @@ where
 
     /// Returns an iterator over all classified values in the map.
     ///
-    /// Each item is a reference to a `ClassifiedData<V>`.
-    pub fn values(&self) -> impl Iterator<Item = &ClassifiedData<V>> {
+    /// Each item is a reference to the stored wrapper `S`.
+    pub fn values(&self) -> impl Iterator<Item = &S> {
         self.inner.values()
     }
 }
@@ -207,6 +256,18 @@ mod tests {
         assert_eq!(values.len(), 2);
     }
 
+    #[test]
+    fn shielded_map_round_trips_values() {
+        let mut map = ShieldedClassifiedMap::new_shielded();
+        map.insert("k", vec![1u8, 2, 3]);
+
+        let value = map.get(&"k").unwrap().expose(|v| v.clone()).unwrap();
+        assert_eq!(value, vec![1, 2, 3]);
+
+        map.remove(&"k");
+        assert!(map.get(&"k").is_none());
+    }
+
     #[test]
     fn overwrite_existing_key() {
         let mut map = ClassifiedMap::new();
@@ -0,0 +1,240 @@
+//! ----------------------------------------------
+//! DOCUMENT DETAILS -----------------------------
+//!
+//! filename:classified_string.rs
+//! description:secret strings such as passwords and API tokens
+//! usages:none in crate yet
+//!
+//! ----------------------------------------------
+//! FEATURE NOTES --------------------------------
+//!
+//! feature_name:async
+//! deps:[tokio][async_trait]
+//! scope:[impl ClassifiedEq]
+//! corpus:true
+//!
+//! feature_name:std
+//! deps:[std]
+//! scope:[struct ClassifiedString]
+//! corpus:false
+//!
+//! ----------------------------------------------
+//! CORPUS FEATURES ------------------------------
+//!
+#![cfg(feature = "async")]
+#![cfg(feature = "std")]
+
+//! Passwords, API tokens and other secret text.
+//!
+//! [`ClassifiedString`] compares in constant time, can check a token prefix
+//! such as `sk_live_` in constant time, and produces UTF-8 safe redactions
+//! for support UIs. It deliberately does not implement `Display`, so it
+//! cannot end up in a format string by accident.
+
+use std::fmt;
+
+use secrecy::{ExposeSecret, SecretBox};
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer,
+};
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::Zeroize;
+
+use crate::traits::ClassifiedEq;
+
+/// Mask shown in place of the hidden part of a redacted string.
+const MASK: &str = "****";
+
+/// A secret string, zeroized on drop.
+///
+/// # Example
+/// ```
+/// use classified::classified_string::ClassifiedString;
+///
+/// let token = ClassifiedString::new("sk_live_51Habc9xyz");
+/// assert!(bool::from(token.ct_starts_with("sk_live_")));
+/// assert_eq!(token.reveal_last(4), "****9xyz");
+/// ```
+pub struct ClassifiedString {
+    data: SecretBox<String>,
+}
+
+impl ClassifiedString {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self {
+            data: SecretBox::new(Box::new(value.into())),
+        }
+    }
+
+    /// Expose the secret text.
+    ///
+    /// ⚠️ Use with care. This is a read-only view of sensitive data.
+    #[must_use = "You must never ignore confidential data"]
+    pub fn expose(&self) -> &str {
+        self.data.expose_secret()
+    }
+
+    /// Length in bytes.
+    pub fn len(&self) -> usize {
+        self.expose().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.expose().is_empty()
+    }
+
+    /// Whether the secret starts with `prefix`.
+    ///
+    /// Runs in time that depends only on the lengths of `prefix` and the
+    /// secret, never on where the first mismatching byte is.
+    pub fn ct_starts_with(&self, prefix: &str) -> Choice {
+        let secret = self.expose().as_bytes();
+        let prefix = prefix.as_bytes();
+
+        let long_enough = Choice::from((secret.len() >= prefix.len()) as u8);
+        let mut equal = Choice::from(1);
+        for (i, expected) in prefix.iter().enumerate() {
+            let actual = secret.get(i).copied().unwrap_or(0);
+            equal &= actual.ct_eq(expected);
+        }
+        long_enough & equal
+    }
+
+    /// A redacted form showing only the last `n` characters, e.g. `****9xyz`.
+    ///
+    /// Counts characters, not bytes, so multi-byte text is never split. If
+    /// the secret has no more than `n` characters, nothing is revealed. The
+    /// mask has a fixed width and does not leak the secret's length.
+    pub fn reveal_last(&self, n: usize) -> String {
+        let secret = self.expose();
+        let total = secret.chars().count();
+        if n == 0 || total <= n {
+            return MASK.to_owned();
+        }
+        let start = secret
+            .char_indices()
+            .nth(total - n)
+            .map_or(secret.len(), |(i, _)| i);
+        format!("{MASK}{}", &secret[start..])
+    }
+}
+
+impl From<String> for ClassifiedString {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl ConstantTimeEq for ClassifiedString {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.expose().as_bytes().ct_eq(other.expose().as_bytes())
+    }
+}
+
+impl ClassifiedEq for ClassifiedString {
+    fn classified_eq(&self, rhs: &Self) -> bool {
+        self.ct_eq(rhs).into()
+    }
+}
+
+impl Drop for ClassifiedString {
+    fn drop(&mut self) {
+        self.data.zeroize();
+    }
+}
+
+impl fmt::Debug for ClassifiedString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ClassifiedString(<REDACTED>)")
+    }
+}
+
+/// Deserializes without an intermediate plain `String`.
+///
+/// Owned input is moved straight into secret storage; borrowed input is
+/// copied into it once.
+impl<'de> Deserialize<'de> for ClassifiedString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_string(ClassifiedStringVisitor)
+    }
+}
+
+struct ClassifiedStringVisitor;
+
+impl Visitor<'_> for ClassifiedStringVisitor {
+    type Value = ClassifiedString;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a secret string")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(ClassifiedString::new(value))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+        Ok(ClassifiedString::new(value))
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+        std::str::from_utf8(value)
+            .map(ClassifiedString::new)
+            .map_err(|_| E::invalid_value(de::Unexpected::Other("non-UTF-8 bytes"), &self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_in_constant_time() {
+        let a = ClassifiedString::new("hunter2");
+        let b = ClassifiedString::new("hunter2");
+        let c = ClassifiedString::new("hunter3");
+
+        assert!(a.classified_eq(&b));
+        assert!(!a.classified_eq(&c));
+        assert!(!a.classified_eq(&ClassifiedString::new("hunter")));
+    }
+
+    #[test]
+    fn checks_prefix() {
+        let token = ClassifiedString::new("sk_live_abc");
+        assert!(bool::from(token.ct_starts_with("sk_live_")));
+        assert!(bool::from(token.ct_starts_with("")));
+        assert!(!bool::from(token.ct_starts_with("sk_test_")));
+        assert!(!bool::from(token.ct_starts_with("sk_live_abcdef")));
+    }
+
+    #[test]
+    fn reveal_last_is_utf8_safe() {
+        let secret = ClassifiedString::new("pässwörd");
+        assert_eq!(secret.reveal_last(3), "****örd");
+        assert_eq!(secret.reveal_last(8), "****");
+        assert_eq!(secret.reveal_last(0), "****");
+        assert_eq!(ClassifiedString::new("日本語テキスト").reveal_last(2), "****スト");
+    }
+
+    #[test]
+    fn debug_is_redacted() {
+        let secret = ClassifiedString::new("hunter2");
+        assert_eq!(format!("{:?}", secret), "ClassifiedString(<REDACTED>)");
+    }
+
+    #[test]
+    fn deserializes_from_toml() {
+        #[derive(Deserialize)]
+        struct Credentials {
+            api_token: ClassifiedString,
+        }
+
+        let creds: Credentials = toml::from_str(r#"api_token = "sk_live_123""#).unwrap();
+        assert_eq!(creds.api_token.expose(), "sk_live_123");
+        assert!(toml::from_str::<Credentials>("api_token = 5").is_err());
+    }
+}
@@ -41,8 +41,8 @@ use tokio::sync::{
 use crate::{
     config::StageConfig,
     crypto::{
-        crypto_algorithm::CryptoAlgorithm,
-        crypto_primitive::CryptoPrimitive
+        crypto_primitive::CryptoPrimitive,
+        helpers::parse_algorithm
     },
     errors::{
         CryptoError, SecureError, ClassifiedError
@@ -195,36 +195,25 @@ impl PipelineStage for EncryptionActor {
 pub fn create_actor(
     cfg: &StageConfig
 ) -> Result<Arc<dyn PipelineStage>, SecureError> {
-    if let Some(algo) = &cfg.algorithm {
-        let algo = match algo.as_str() {
-            "AES" => CryptoAlgorithm::AES,
-            "RSA" => CryptoAlgorithm::RSA,
-            "ECDSA" => CryptoAlgorithm::ECDSA,
-            _ => return Err(SecureError::PipelineError(
-                format!("Unsupported algorithm: {}", algo)
-            )),
-        };
-        
-        let key_material = cfg.key_material.clone()
-            .ok_or_else(|| SecureError::PipelineError(
-                "Missing key material".into()
-            ))?;
-        
-        let zeroize = cfg.zeroize.unwrap_or(true);
-        
-        let crypto = CryptoPrimitive::new(
-            &algo,
-            key_material,
-            zeroize
-        )
-            .map_err(|e| SecureError::PipelineError(
-                format!("Init failed: {}", e)
-            ))?;
-    
-        Ok(Arc::new(EncryptionActor::new(crypto, None)))
-    } else {
-        Err(SecureError::PipelineError("Missing algorithm".into()))
-    }
+    let algo = parse_algorithm(&cfg.algorithm)?;
+
+    let key_material = cfg.key_material.clone()
+        .ok_or_else(|| SecureError::PipelineError(
+            "Missing key material".into()
+        ))?;
+
+    let zeroize = cfg.zeroize.unwrap_or(true);
+
+    let crypto = CryptoPrimitive::new(
+        &algo,
+        key_material,
+        zeroize
+    )
+        .map_err(|e| SecureError::PipelineError(
+            format!("Init failed: {}", e)
+        ))?;
+
+    Ok(Arc::new(EncryptionActor::new(crypto, None)))
 }
 #[async_trait::async_trait]
 impl Actor for dyn PipelineStage {
@@ -281,6 +270,27 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_create_actor_chacha_variants() {
+        for name in ["ChaCha20-Poly1305", "XChaCha20-Poly1305"] {
+            let mut cfg = test_stage_config();
+            cfg.algorithm = Some(name.to_string());
+            assert!(create_actor(&cfg).is_ok());
+            assert!(crate::pipelines::create_actor(&cfg).is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_actor_names_match_parse_algorithm() {
+        for name in ["AES", "AES-256", "ChaCha20-Poly1305", "XChaCha20-Poly1305", "SHA256"] {
+            let mut cfg = test_stage_config();
+            cfg.algorithm = Some(name.to_string());
+            let accepted = crate::crypto::helpers::parse_algorithm(&cfg.algorithm).is_ok();
+            assert_eq!(create_actor(&cfg).is_ok(), accepted, "{name}");
+            assert_eq!(crate::pipelines::create_actor(&cfg).is_ok(), accepted, "{name}");
+        }
+    }
+
     #[tokio::test]
     async fn test_create_actor_missing_algorithm() {
         // let mut cfg = valid_config();
@@ -31,6 +31,8 @@ use serde::Deserialize;
 use std::{fs, sync::LazyLock};
 
 use crate::{
+    access_control::AccessChecker,
+    classification::Compartment,
     errors::{
         SecureError,
         ClassifiedError
@@ -54,6 +56,49 @@ pub struct Config {
 #[derive(Deserialize, Clone)]
 pub struct SecurityConfig {
     pub enable_zeroize: bool,
+    #[serde(default)]
+    pub access_control: AccessControlConfig,
+}
+
+impl SecurityConfig {
+    /// Build the label checker described by the `[security.access_control]` table.
+    pub fn access_checker(&self) -> AccessChecker {
+        AccessChecker::from_config(&self.access_control)
+    }
+}
+
+/// Mandatory access control settings.
+///
+/// ```toml
+/// [security.access_control]
+/// enforce = true            # false logs violations instead of refusing them
+/// allow_write_down = false  # relax the *-property for trusted pipelines
+/// compartments = ["PII", "FINANCE", "EXPORT"]
+/// ```
+#[derive(Deserialize, Clone)]
+pub struct AccessControlConfig {
+    #[serde(default = "enforce_by_default")]
+    pub enforce: bool,
+    #[serde(default)]
+    pub allow_write_down: bool,
+    /// Known compartments. Labels naming any other compartment are rejected.
+    /// Empty means any compartment is accepted.
+    #[serde(default)]
+    pub compartments: Vec<Compartment>,
+}
+
+fn enforce_by_default() -> bool {
+    true
+}
+
+impl Default for AccessControlConfig {
+    fn default() -> Self {
+        Self {
+            enforce: true,
+            allow_write_down: false,
+            compartments: Vec::new(),
+        }
+    }
 }
 
 
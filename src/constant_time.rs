@@ -0,0 +1,436 @@
+//! ----------------------------------------------
+//! DOCUMENT DETAILS -----------------------------
+//!
+//! filename:constant_time.rs
+//! description:constant-time ordering, selection and encodings for secrets
+//! usages:[classified_data.rs][sized_classified_data.rs][exposure_aware_classified_data.rs]
+//!
+//! ----------------------------------------------
+//! FEATURE NOTES --------------------------------
+//!
+//! feature_name:async
+//! deps:[tokio][async_trait]
+//! scope:[trait ConstantTimeOps]
+//! corpus:true
+//!
+//! feature_name:std
+//! deps:[std]
+//! scope:[trait ConstantTimeOps]
+//! corpus:false
+//!
+//! ----------------------------------------------
+//! CORPUS FEATURES ------------------------------
+//!
+#![cfg(feature = "async")]
+#![cfg(feature = "std")]
+
+//! Constant-time operations beyond equality.
+//!
+//! Everything here runs in time that depends only on the lengths of its
+//! inputs, never on their contents:
+//!
+//! - [`ct_lt`] / [`ct_gt`] order byte strings lexicographically.
+//! - [`ct_conditional_swap`] swaps two buffers when a [`Choice`] is set.
+//! - [`hex_encode`] / [`hex_decode`] and [`base64_encode`] /
+//!   [`base64_decode`] compute every character arithmetically. Table-based
+//!   encoders index memory with secret bytes and leak them through the cache.
+//!
+//! [`ConstantTimeOps`] offers the same operations on the classified wrappers;
+//! it is implemented with the [`impl_ct_ops!`](crate::impl_ct_ops) macro.
+//! On [`ExposureAwareClassifiedData`](crate::exposure_aware_classified_data::ExposureAwareClassifiedData)
+//! every operation counts as an exposure of each secret it reads.
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeGreater, ConstantTimeLess};
+use zeroize::Zeroize;
+
+use crate::{classified_string::ClassifiedString, errors::EncodingError};
+
+/// Whether `a` sorts before `b`, comparing bytes lexicographically.
+///
+/// A proper prefix sorts before the longer string.
+pub fn ct_lt(a: &[u8], b: &[u8]) -> Choice {
+    let mut lt = Choice::from(0);
+    let mut decided = Choice::from(0);
+
+    for i in 0..a.len().max(b.len()) {
+        // Lengths are public, so branching on them leaks nothing.
+        let (less, greater) = match (a.get(i), b.get(i)) {
+            (Some(x), Some(y)) => (x.ct_lt(y), x.ct_gt(y)),
+            (None, _) => (Choice::from(1), Choice::from(0)),
+            (_, None) => (Choice::from(0), Choice::from(1)),
+        };
+        lt.conditional_assign(&less, !decided);
+        decided |= less | greater;
+    }
+    lt
+}
+
+/// Whether `a` sorts after `b`; see [`ct_lt`].
+pub fn ct_gt(a: &[u8], b: &[u8]) -> Choice {
+    ct_lt(b, a)
+}
+
+/// Swap the contents of `a` and `b` if `choice` is set.
+///
+/// # Panics
+/// Panics if the buffers differ in length.
+pub fn ct_conditional_swap(a: &mut [u8], b: &mut [u8], choice: Choice) {
+    assert_eq!(a.len(), b.len(), "conditional swap needs equal lengths");
+    for (x, y) in a.iter_mut().zip(b.iter_mut()) {
+        u8::conditional_swap(x, y, choice);
+    }
+}
+
+/// `0xff` if `x < y`, otherwise `0`.
+fn mask_lt(x: u8, y: u8) -> u8 {
+    ((x as u16).wrapping_sub(y as u16) >> 8) as u8
+}
+
+/// `0xff` if `x == y`, otherwise `0`.
+fn mask_eq(x: u8, y: u8) -> u8 {
+    (((x ^ y) as u16).wrapping_sub(1) >> 8) as u8
+}
+
+/// `0xff` if `lo <= x <= hi`, otherwise `0`.
+fn mask_in(x: u8, lo: u8, hi: u8) -> u8 {
+    !mask_lt(x, lo) & !mask_lt(hi, x)
+}
+
+fn nibble_to_hex(n: u8) -> u8 {
+    // '0'..='9' for 0..=9, then 'a'..='f' ('a' is 0x27 past '9' + 1).
+    n + b'0' + (!mask_lt(n, 10) & 0x27)
+}
+
+/// The value of a hex digit, and `0xff` if it is valid.
+fn hex_to_nibble(c: u8) -> (u8, u8) {
+    let digit = mask_in(c, b'0', b'9');
+    let lower = mask_in(c, b'a', b'f');
+    let upper = mask_in(c, b'A', b'F');
+    let value = (digit & c.wrapping_sub(b'0'))
+        | (lower & c.wrapping_sub(b'a' - 10))
+        | (upper & c.wrapping_sub(b'A' - 10));
+    (value, digit | lower | upper)
+}
+
+/// Lowercase hex encoding of `bytes`.
+pub fn hex_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        out.push(nibble_to_hex(byte >> 4) as char);
+        out.push(nibble_to_hex(byte & 0x0f) as char);
+    }
+    out
+}
+
+/// Decode hex in either case.
+///
+/// Invalid input is detected only after the whole string has been read, and
+/// the error does not say where.
+pub fn hex_decode(encoded: &str) -> Result<Vec<u8>, EncodingError> {
+    let encoded = encoded.as_bytes();
+    if !encoded.len().is_multiple_of(2) {
+        return Err(EncodingError::InvalidLength(encoded.len()));
+    }
+
+    let mut out = Vec::with_capacity(encoded.len() / 2);
+    let mut valid = 0xffu8;
+    for pair in encoded.chunks_exact(2) {
+        let (hi, hi_ok) = hex_to_nibble(pair[0]);
+        let (lo, lo_ok) = hex_to_nibble(pair[1]);
+        valid &= hi_ok & lo_ok;
+        out.push((hi << 4) | lo);
+    }
+    finish(out, valid)
+}
+
+const BASE64_PAD: u8 = b'=';
+
+fn sextet_to_base64(x: u8) -> u8 {
+    (mask_lt(x, 26) & x.wrapping_add(b'A'))
+        | (mask_in(x, 26, 51) & x.wrapping_add(b'a' - 26))
+        | (mask_in(x, 52, 61) & x.wrapping_sub(52 - b'0'))
+        | (mask_eq(x, 62) & b'+')
+        | (mask_eq(x, 63) & b'/')
+}
+
+/// The value of a base64 character, and `0xff` if it is valid.
+fn base64_to_sextet(c: u8) -> (u8, u8) {
+    let upper = mask_in(c, b'A', b'Z');
+    let lower = mask_in(c, b'a', b'z');
+    let digit = mask_in(c, b'0', b'9');
+    let plus = mask_eq(c, b'+');
+    let slash = mask_eq(c, b'/');
+    let value = (upper & c.wrapping_sub(b'A'))
+        | (lower & c.wrapping_sub(b'a' - 26))
+        | (digit & c.wrapping_add(52 - b'0'))
+        | (plus & 62)
+        | (slash & 63);
+    (value, upper | lower | digit | plus | slash)
+}
+
+/// Standard, padded base64 encoding of `bytes`.
+pub fn base64_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let mut b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let mut sextets = [
+            b[0] >> 2,
+            ((b[0] & 0x03) << 4) | (b[1] >> 4),
+            ((b[1] & 0x0f) << 2) | (b[2] >> 6),
+            b[2] & 0x3f,
+        ];
+        // Chunk lengths are public, so padding may depend on them.
+        for (i, sextet) in sextets.iter().enumerate() {
+            let c = if i <= chunk.len() { sextet_to_base64(*sextet) } else { BASE64_PAD };
+            out.push(c as char);
+        }
+        b.zeroize();
+        sextets.zeroize();
+    }
+    out
+}
+
+/// Decode standard, padded base64.
+///
+/// Invalid input is detected only after the whole string has been read, and
+/// the error does not say where.
+pub fn base64_decode(encoded: &str) -> Result<Vec<u8>, EncodingError> {
+    let encoded = encoded.as_bytes();
+    if !encoded.len().is_multiple_of(4) {
+        return Err(EncodingError::InvalidLength(encoded.len()));
+    }
+    // Padding only reveals the length of the output, which is public.
+    let padding = encoded.iter().rev().take(2).take_while(|&&c| c == BASE64_PAD).count();
+
+    let mut out = Vec::with_capacity(encoded.len() / 4 * 3);
+    let mut valid = 0xffu8;
+    for (n, chunk) in encoded.chunks_exact(4).enumerate() {
+        let last = n == encoded.len() / 4 - 1;
+        let mut sextets = [0u8; 4];
+        for (i, c) in chunk.iter().enumerate() {
+            if last && i >= 4 - padding {
+                continue;
+            }
+            let (value, ok) = base64_to_sextet(*c);
+            sextets[i] = value;
+            valid &= ok;
+        }
+        let mut bytes = [
+            (sextets[0] << 2) | (sextets[1] >> 4),
+            (sextets[1] << 4) | (sextets[2] >> 2),
+            (sextets[2] << 6) | sextets[3],
+        ];
+        let keep = if last { 3 - padding } else { 3 };
+        out.extend_from_slice(&bytes[..keep]);
+        // Canonical encodings leave the bits after the last byte clear.
+        for extra in &bytes[keep..] {
+            valid &= mask_eq(*extra, 0);
+        }
+        sextets.zeroize();
+        bytes.zeroize();
+    }
+    finish(out, valid)
+}
+
+fn finish(mut out: Vec<u8>, valid: u8) -> Result<Vec<u8>, EncodingError> {
+    if valid == 0xff {
+        Ok(out)
+    } else {
+        out.zeroize();
+        Err(EncodingError::InvalidCharacter)
+    }
+}
+
+pub(crate) mod sealed {
+    use crate::errors::EncodingError;
+
+    /// Raw byte access for [`ConstantTimeOps`](super::ConstantTimeOps).
+    ///
+    /// Sealed so the bytes are only reachable through constant-time code.
+    pub trait ConstantTimeBytes {
+        fn ct_bytes(&self) -> &[u8];
+        fn ct_bytes_mut(&mut self) -> &mut [u8];
+        fn from_ct_bytes(bytes: Vec<u8>) -> Result<Self, EncodingError>
+        where
+            Self: Sized;
+
+        /// Wrap the bytes selected from `a` and `b`, carrying over whatever
+        /// labels both inputs hold.
+        fn ct_select_from(a: &Self, b: &Self, bytes: Vec<u8>) -> Result<Self, EncodingError>
+        where
+            Self: Sized,
+        {
+            let _ = (a, b);
+            Self::from_ct_bytes(bytes)
+        }
+
+        /// Reconcile the two secrets' labels or policies before their bytes
+        /// are swapped.
+        fn ct_before_swap(&mut self, other: &mut Self) {
+            let _ = other;
+        }
+    }
+}
+
+/// Constant-time ordering, selection and encodings for classified bytes.
+///
+/// Encoding a secret yields another secret: the output is a
+/// [`ClassifiedString`] and is not treated as an exposure.
+///
+/// # Example
+/// ```
+/// use classified::classified_data::ClassifiedData;
+/// use classified::constant_time::ConstantTimeOps;
+///
+/// let key = ClassifiedData::new(vec![0xde, 0xad, 0xbe, 0xef]);
+/// assert_eq!(key.to_hex().expose(), "deadbeef");
+///
+/// let back = ClassifiedData::<Vec<u8>>::from_base64(key.to_base64().expose()).unwrap();
+/// assert!(bool::from(key.ct_lt(&ClassifiedData::new(vec![0xff]))));
+/// assert_eq!(back.expose(), key.expose());
+/// ```
+pub trait ConstantTimeOps: sealed::ConstantTimeBytes {
+    fn ct_lt(&self, other: &Self) -> Choice {
+        ct_lt(self.ct_bytes(), other.ct_bytes())
+    }
+
+    fn ct_gt(&self, other: &Self) -> Choice {
+        ct_gt(self.ct_bytes(), other.ct_bytes())
+    }
+
+    /// Swap the two secrets if `choice` is set.
+    ///
+    /// Both secrets end up with the labels of either, whatever `choice` is,
+    /// so the labels reveal nothing about it.
+    ///
+    /// # Panics
+    /// Panics if the secrets differ in length.
+    fn ct_conditional_swap(&mut self, other: &mut Self, choice: Choice) {
+        self.ct_before_swap(other);
+        ct_conditional_swap(self.ct_bytes_mut(), other.ct_bytes_mut(), choice);
+    }
+
+    /// A copy of `a` if `choice` is 0, or of `b` if it is 1.
+    ///
+    /// The copy carries the labels of both inputs.
+    ///
+    /// # Panics
+    /// Panics if the secrets differ in length.
+    fn ct_conditional_select(a: &Self, b: &Self, choice: Choice) -> Self
+    where
+        Self: Sized,
+    {
+        let mut selected = a.ct_bytes().to_vec();
+        let other = b.ct_bytes();
+        assert_eq!(selected.len(), other.len(), "conditional select needs equal lengths");
+        for (x, y) in selected.iter_mut().zip(other) {
+            x.conditional_assign(y, choice);
+        }
+        Self::ct_select_from(a, b, selected).expect("selected bytes have the length of the inputs")
+    }
+
+    fn to_hex(&self) -> ClassifiedString {
+        ClassifiedString::new(hex_encode(self.ct_bytes()))
+    }
+
+    fn to_base64(&self) -> ClassifiedString {
+        ClassifiedString::new(base64_encode(self.ct_bytes()))
+    }
+
+    fn from_hex(encoded: &str) -> Result<Self, EncodingError>
+    where
+        Self: Sized,
+    {
+        Self::from_ct_bytes(hex_decode(encoded)?)
+    }
+
+    fn from_base64(encoded: &str) -> Result<Self, EncodingError>
+    where
+        Self: Sized,
+    {
+        Self::from_ct_bytes(base64_decode(encoded)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_lexicographically() {
+        assert!(bool::from(ct_lt(b"abc", b"abd")));
+        assert!(bool::from(ct_lt(b"ab", b"abc")));
+        assert!(bool::from(ct_lt(b"", b"a")));
+        assert!(!bool::from(ct_lt(b"abc", b"abc")));
+        assert!(!bool::from(ct_lt(b"b", b"abc")));
+        assert!(bool::from(ct_gt(b"b", b"abc")));
+        assert!(!bool::from(ct_gt(b"abc", b"abc")));
+    }
+
+    #[test]
+    fn swaps_only_when_chosen() {
+        let mut a = *b"left";
+        let mut b = *b"rite";
+        ct_conditional_swap(&mut a, &mut b, Choice::from(0));
+        assert_eq!(&a, b"left");
+        ct_conditional_swap(&mut a, &mut b, Choice::from(1));
+        assert_eq!((&a, &b), (b"rite", b"left"));
+    }
+
+    #[test]
+    fn hex_round_trips_every_byte() {
+        let all: Vec<u8> = (0..=255).collect();
+        let encoded = hex_encode(&all);
+        assert_eq!(&encoded[..8], "00010203");
+        assert_eq!(&encoded[encoded.len() - 4..], "feff");
+        assert_eq!(hex_decode(&encoded).unwrap(), all);
+        assert_eq!(hex_decode("DEADbeef").unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+    }
+
+    #[test]
+    fn hex_rejects_bad_input() {
+        assert_eq!(hex_decode("abc"), Err(EncodingError::InvalidLength(3)));
+        assert_eq!(hex_decode("0g"), Err(EncodingError::InvalidCharacter));
+        assert_eq!(hex_decode("/:"), Err(EncodingError::InvalidCharacter));
+    }
+
+    #[test]
+    fn base64_matches_rfc4648_vectors() {
+        let vectors = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+        for (plain, encoded) in vectors {
+            assert_eq!(base64_encode(plain.as_bytes()), encoded);
+            assert_eq!(base64_decode(encoded).unwrap(), plain.as_bytes());
+        }
+    }
+
+    #[test]
+    fn base64_round_trips_every_byte() {
+        let all: Vec<u8> = (0..=255).collect();
+        let encoded = base64_encode(&all);
+        assert!(encoded.contains('+') && encoded.contains('/'));
+        assert_eq!(base64_decode(&encoded).unwrap(), all);
+    }
+
+    #[test]
+    fn base64_rejects_bad_input() {
+        assert_eq!(base64_decode("Zm9"), Err(EncodingError::InvalidLength(3)));
+        assert_eq!(base64_decode("Zm9-"), Err(EncodingError::InvalidCharacter));
+        assert_eq!(base64_decode("Z=9v"), Err(EncodingError::InvalidCharacter));
+    }
+
+    #[test]
+    fn base64_rejects_non_zero_trailing_bits() {
+        assert_eq!(base64_decode("Zh=="), Err(EncodingError::InvalidCharacter));
+        assert_eq!(base64_decode("Zm9="), Err(EncodingError::InvalidCharacter));
+        assert_eq!(base64_decode("Zg==").unwrap(), b"f");
+        assert_eq!(base64_decode("Zm8=").unwrap(), b"fo");
+    }
+}
@@ -30,5 +30,8 @@ pub mod crypto_builder;
 // pub(crate) mod crypto_primitive;
 pub mod crypto_primitive;
 pub(crate) mod crypto_stage;
+pub mod key_generator;
 pub mod key_length;
+pub mod signature;
+pub mod signing_stage;
 pub mod helpers;
@@ -42,9 +42,18 @@
 
 
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CryptoAlgorithm {
     RSA,
+    /// ECDSA over P-256 with SHA-256.
     ECDSA,
+    /// ECDSA over P-384 with SHA-384.
+    EcdsaP384,
+    /// EdDSA over Curve25519 (RFC 8032).
+    Ed25519,
     AES,
+    /// RFC 8439 AEAD with a 96-bit nonce; fast without AES hardware.
+    ChaCha20Poly1305,
+    /// ChaCha20-Poly1305 with a 192-bit nonce, safe to pick at random.
+    XChaCha20Poly1305,
 }
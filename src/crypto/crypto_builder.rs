@@ -51,10 +51,9 @@
 
 
 // Phase 1: Implement Core Structs and Macros for Secure Concurrency and Cryptographic Library
-use secrecy::{SecretBox};
-
 use super::crypto_algorithm::CryptoAlgorithm;
 use super::crypto_primitive::CryptoPrimitive;
+use super::key_generator::KeyGenerator;
 use super::key_length::KeyLength;
 use crate::errors::SecureError;
 
@@ -93,21 +92,10 @@ impl CryptoBuilder {
         let key_length = self.key_length.ok_or_else(|| {
             SecureError::PipelineError("Key length must be specified".into())
         })?;
+        key_length.check_for(&self.algorithm)?;
 
-        // Initialize key material based on algorithm and key length
-        // Placeholder key material generation
-        let key_material = match self.algorithm {
-            CryptoAlgorithm::AES => vec![0u8; 32], // AES-256
-            CryptoAlgorithm::RSA => vec![0u8; 256], // Placeholder for RSA key
-            CryptoAlgorithm::ECDSA => vec![0u8; 256], // Placeholder for ECDSA key
-        };
-
-        Ok(CryptoPrimitive {
-            algorithm: self.algorithm,
-            key_length,
-            key_material: SecretBox::new(Box::new(key_material)),
-            zeroize: self.zeroize,
-        })
+        KeyGenerator::generate(&self.algorithm, &key_length, self.zeroize)
+            .map_err(|e| SecureError::PipelineError(e.to_string()))
     }
 }
 
@@ -122,6 +110,7 @@ mod tests {
         key_length::KeyLength,
     };
 
+    #[cfg(feature = "rsa")]
     #[test]
     fn builds_with_default_rsa_settings() {
         let builder = CryptoBuilder::new().key_length(KeyLength::Bits2048);
@@ -131,10 +120,20 @@ mod tests {
         let crypto = result.unwrap();
         assert_eq!(crypto.algorithm, CryptoAlgorithm::RSA);
         assert_eq!(crypto.key_length, KeyLength::Bits2048);
-        assert_eq!(crypto.key_material.expose_secret().len(), 256);
+        assert!(crypto.public_key().is_ok());
         assert!(!crypto.zeroize);
     }
 
+    #[test]
+    fn rejects_key_length_for_wrong_algorithm() {
+        let result = CryptoBuilder::new()
+            .algorithm(CryptoAlgorithm::AES)
+            .key_length(KeyLength::Bits2048)
+            .build();
+        assert!(matches!(result, Err(SecureError::InvalidKeyLength)));
+        assert!(CryptoBuilder::new().key_length(KeyLength::Bits1024).build().is_err());
+    }
+
     #[test]
     fn builds_with_aes_and_zeroize() {
         let builder = CryptoBuilder::new()
@@ -164,7 +163,22 @@ mod tests {
         let crypto = result.unwrap();
         assert_eq!(crypto.algorithm, CryptoAlgorithm::ECDSA);
         assert_eq!(crypto.key_length, KeyLength::Bits256);
-        assert_eq!(crypto.key_material.expose_secret().len(), 256);
+        assert_eq!(crypto.key_material.expose_secret().len(), 32);
+        assert!(crypto.public_key().is_ok());
+    }
+
+    #[test]
+    fn builds_random_keys() {
+        let build = || {
+            CryptoBuilder::new()
+                .algorithm(CryptoAlgorithm::AES)
+                .key_length(KeyLength::Bits256)
+                .build()
+                .unwrap()
+        };
+        let (a, b) = (build(), build());
+        assert_ne!(a.key_material.expose_secret(), &vec![0u8; 32]);
+        assert_ne!(a.key_material.expose_secret(), b.key_material.expose_secret());
     }
 
     #[cfg(feature = "std")]
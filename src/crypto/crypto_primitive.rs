@@ -21,6 +21,12 @@
 //! effected_lines:[]
 //! corpus:false
 //! 
+//! feature_name:rsa
+//! deps:[rsa]
+//! scope:[fn generate_rsa][RSA encryption, decryption and signing]
+//! effected_lines:[]
+//! corpus:false
+//! 
 //! 
 //! 
 //! 
@@ -49,16 +55,37 @@
 
 
 
-use secrecy::{SecretBox};
+use aes_gcm::{
+    aead::{self, Aead, KeyInit, Payload},
+    Aes256Gcm,
+};
+use chacha20poly1305::{ChaCha20Poly1305, XChaCha20Poly1305};
+#[cfg(feature = "rsa")]
+use rand_core::OsRng;
+#[cfg(feature = "rsa")]
+use rsa::{
+    pkcs1::{DecodeRsaPrivateKey, EncodeRsaPrivateKey, EncodeRsaPublicKey},
+    Oaep, RsaPrivateKey,
+};
+use secrecy::{ExposeSecret, SecretBox};
+#[cfg(feature = "rsa")]
+use sha2::Sha256;
 use zeroize::Zeroize;
 
 use crate::errors::{CryptoError};
 use super::{
     crypto_algorithm::CryptoAlgorithm,
-    key_length::KeyLength
+    key_length::KeyLength,
+    signature::{PublicKey, Signature},
 };
 
 
+/// AES-GCM nonce length (96 bits, as recommended by SP 800-38D).
+pub const AES_GCM_NONCE_LEN: usize = 12;
+/// AES-GCM authentication tag length.
+pub const AES_GCM_TAG_LEN: usize = 16;
+/// XChaCha20-Poly1305 nonce length (192 bits); ChaCha20-Poly1305 uses 96.
+pub const XCHACHA_NONCE_LEN: usize = 24;
 
 #[derive(Debug)]
 pub struct CryptoPrimitive {
@@ -68,14 +95,40 @@ pub struct CryptoPrimitive {
     pub zeroize: bool,
 }
 impl CryptoPrimitive {
-    pub fn new(algorithm: &CryptoAlgorithm, key_material: Vec<u8>, zeroize: bool) -> Result<Self, CryptoError> {
+    /// Wrap existing key material.
+    ///
+    /// Symmetric, ECDSA and Ed25519 keys are raw bytes of exactly the
+    /// algorithm's key length. RSA keys are PKCS#1 DER private keys of 2048,
+    /// 3072 or 4096 bits, as produced by [`generate_rsa`](Self::generate_rsa);
+    /// without the `rsa` feature they fail with [`CryptoError::RSADisabled`].
+    pub fn new(algorithm: &CryptoAlgorithm, mut key_material: Vec<u8>, zeroize: bool) -> Result<Self, CryptoError> {
+        if *algorithm == CryptoAlgorithm::RSA {
+            let key_length = rsa_der_key_length(&key_material);
+            return match key_length {
+                Ok(key_length) => Ok(Self {
+                    algorithm: CryptoAlgorithm::RSA,
+                    key_length,
+                    key_material: SecretBox::new(Box::new(key_material)),
+                    zeroize,
+                }),
+                Err(e) => {
+                    key_material.zeroize();
+                    Err(e)
+                }
+            };
+        }
+
         let key_length = match algorithm {
             CryptoAlgorithm::AES => KeyLength::Bits256,
-            CryptoAlgorithm::RSA => KeyLength::Bits2048,
+            CryptoAlgorithm::RSA => unreachable!("RSA keys are handled above"),
             CryptoAlgorithm::ECDSA => KeyLength::Bits256,
+            CryptoAlgorithm::EcdsaP384 => KeyLength::Bits384,
+            CryptoAlgorithm::Ed25519 => KeyLength::Bits256,
+            CryptoAlgorithm::ChaCha20Poly1305 | CryptoAlgorithm::XChaCha20Poly1305 => KeyLength::Bits256,
         };
 
         if key_material.len() != key_length.as_bytes() {
+            key_material.zeroize();
             return Err(CryptoError::CryptoError("Key length mismatch".to_string()));
         }
 
@@ -88,47 +141,304 @@ impl CryptoPrimitive {
     }
 }
 
+impl CryptoPrimitive {
+    /// Generate a fresh RSA key of 2048, 3072 or 4096 bits.
+    ///
+    /// Key generation is slow (seconds for 4096 bits); run it off the async
+    /// executor, e.g. with `tokio::task::spawn_blocking`. Fails with
+    /// [`CryptoError::RSADisabled`] without the `rsa` feature.
+    pub fn generate_rsa(key_length: &KeyLength, zeroize: bool) -> Result<Self, CryptoError> {
+        key_length.check_for(&CryptoAlgorithm::RSA)?;
+
+        #[cfg(not(feature = "rsa"))]
+        {
+            let _ = zeroize;
+            Err(CryptoError::RSADisabled)
+        }
+        #[cfg(feature = "rsa")]
+        {
+            let key = RsaPrivateKey::new(&mut OsRng, key_length.as_bits())
+                .map_err(|e| CryptoError::KeyGenerationError(e.to_string()))?;
+            // `SecretDocument` wipes its own buffer; only our copy survives.
+            let der = key
+                .to_pkcs1_der()
+                .map_err(|e| CryptoError::CryptoError(format!("RSA key encoding failed: {e}")))?;
+
+            Ok(Self {
+                algorithm: CryptoAlgorithm::RSA,
+                key_length: key_length.clone(),
+                key_material: SecretBox::new(Box::new(der.as_bytes().to_vec())),
+                zeroize,
+            })
+        }
+    }
+}
+
 impl CryptoPrimitive {
     // Implement encryption, decryption, etc.
     pub fn encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        self.encrypt_with_aad(data, &[])
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        self.decrypt_with_aad(data, &[])
+    }
+
+    /// Encrypt `data`, binding `aad` into the authentication tag.
+    ///
+    /// For the AEAD ciphers (AES-GCM, ChaCha20-Poly1305, XChaCha20-Poly1305)
+    /// the output is `nonce || ciphertext || tag`, with a fresh random nonce
+    /// per call. `aad` is not included in the output and
+    /// must be supplied again to decrypt.
+    ///
+    /// RSA uses OAEP with SHA-256 and takes no associated data; the plaintext
+    /// must fit in one block (190 bytes for a 2048-bit key).
+    pub fn encrypt_with_aad(&self, data: &[u8], aad: &[u8]) -> Result<Vec<u8>, CryptoError> {
         match self.algorithm {
-            CryptoAlgorithm::AES => self.aes_encrypt(data),
-            CryptoAlgorithm::RSA => self.rsa_encrypt(data),
-            CryptoAlgorithm::ECDSA => Err(CryptoError::CryptoError(
-                "ECDSA is for signing, not encryption".into(),
-            )),
+            CryptoAlgorithm::AES => self
+                .aead_encrypt::<Aes256Gcm>(data, aad)
+                .map_err(|_| CryptoError::AESEncryptionError),
+            CryptoAlgorithm::ChaCha20Poly1305 => self
+                .aead_encrypt::<ChaCha20Poly1305>(data, aad)
+                .map_err(|_| CryptoError::EncryptionError),
+            CryptoAlgorithm::XChaCha20Poly1305 => self
+                .aead_encrypt::<XChaCha20Poly1305>(data, aad)
+                .map_err(|_| CryptoError::EncryptionError),
+            CryptoAlgorithm::RSA => self.rsa_encrypt(data, aad),
+            CryptoAlgorithm::ECDSA | CryptoAlgorithm::EcdsaP384 | CryptoAlgorithm::Ed25519 => Err(
+                CryptoError::CryptoError(format!("{:?} is for signing, not encryption", self.algorithm)),
+            ),
         }
     }
 
-    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    /// Decrypt the output of [`encrypt_with_aad`](Self::encrypt_with_aad).
+    ///
+    /// For the AEAD ciphers, a wrong key, a wrong `aad` or any tampering
+    /// fails tag verification, with [`CryptoError::AESDecryptionError`] for
+    /// AES and [`CryptoError::DecryptionError`] for the ChaCha variants.
+    /// RSA decryption is blinded and fails with
+    /// [`CryptoError::RSADecryptionError`].
+    ///
+    /// # Security
+    /// The `rsa` crate is affected by RUSTSEC-2023-0071 (the Marvin attack):
+    /// blinding does not make its modular arithmetic constant-time, so an
+    /// attacker who can time many RSA decryptions may recover the key. Do not
+    /// decrypt RSA ciphertexts that arrive over a network.
+    pub fn decrypt_with_aad(&self, data: &[u8], aad: &[u8]) -> Result<Vec<u8>, CryptoError> {
         match self.algorithm {
-            CryptoAlgorithm::AES => self.aes_decrypt(data),
-            CryptoAlgorithm::RSA => self.rsa_decrypt(data),
-            CryptoAlgorithm::ECDSA => Err(CryptoError::CryptoError(
-                "ECDSA is for verification, not decryption".into(),
-            )),
+            CryptoAlgorithm::AES => self
+                .aead_decrypt::<Aes256Gcm>(data, aad)
+                .map_err(|_| CryptoError::AESDecryptionError),
+            CryptoAlgorithm::ChaCha20Poly1305 => self
+                .aead_decrypt::<ChaCha20Poly1305>(data, aad)
+                .map_err(|_| CryptoError::DecryptionError),
+            CryptoAlgorithm::XChaCha20Poly1305 => self
+                .aead_decrypt::<XChaCha20Poly1305>(data, aad)
+                .map_err(|_| CryptoError::DecryptionError),
+            CryptoAlgorithm::RSA => self.rsa_decrypt(data, aad),
+            CryptoAlgorithm::ECDSA | CryptoAlgorithm::EcdsaP384 | CryptoAlgorithm::Ed25519 => Err(
+                CryptoError::CryptoError(format!("{:?} is for verification, not decryption", self.algorithm)),
+            ),
         }
     }
 
-    fn aes_encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CryptoError> {
-        // AES is symmetric; decryption is same as encryption
-        Ok(data.to_vec())
+    /// Sign `msg` with the private key.
+    ///
+    /// ECDSA signatures are deterministic (RFC 6979) and hash the message
+    /// with SHA-256 for P-256 and SHA-384 for P-384. RSA uses PSS with
+    /// SHA-256, a salt as long as the digest, and blinding; like RSA
+    /// decryption it is exposed to RUSTSEC-2023-0071, see
+    /// [`decrypt_with_aad`](Self::decrypt_with_aad).
+    pub fn sign(&self, msg: &[u8]) -> Result<Signature, CryptoError> {
+        use p256::ecdsa::signature::Signer;
+
+        let key = self.key_material.expose_secret();
+        let bytes = match self.algorithm {
+            CryptoAlgorithm::ECDSA => {
+                let key = p256::ecdsa::SigningKey::from_slice(key).map_err(|_| CryptoError::SigningError)?;
+                let sig: p256::ecdsa::Signature = key.try_sign(msg).map_err(|_| CryptoError::SigningError)?;
+                sig.to_bytes().to_vec()
+            }
+            CryptoAlgorithm::EcdsaP384 => {
+                let key = p384::ecdsa::SigningKey::from_slice(key).map_err(|_| CryptoError::SigningError)?;
+                let sig: p384::ecdsa::Signature = key.try_sign(msg).map_err(|_| CryptoError::SigningError)?;
+                sig.to_bytes().to_vec()
+            }
+            CryptoAlgorithm::Ed25519 => self.ed25519_key()?.sign(msg).to_bytes().to_vec(),
+            CryptoAlgorithm::RSA => self.rsa_sign(msg)?,
+            _ => return Err(self.not_a_signer()),
+        };
+        Ok(Signature::new(self.algorithm.clone(), bytes))
+    }
+
+    /// Verify a signature made by [`sign`](Self::sign).
+    pub fn verify(&self, msg: &[u8], signature: &Signature) -> Result<(), CryptoError> {
+        self.public_key()?.verify(msg, signature)
     }
 
-    fn aes_decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CryptoError> {
-        // AES is symmetric; decryption is same as encryption
-        self.aes_encrypt(data)
+    /// Derive the public key, a plain value that may be shared freely.
+    pub fn public_key(&self) -> Result<PublicKey, CryptoError> {
+        let key = self.key_material.expose_secret();
+        let bytes = match self.algorithm {
+            CryptoAlgorithm::ECDSA => p256::ecdsa::SigningKey::from_slice(key)
+                .map_err(|_| CryptoError::InvalidKeyLength)?
+                .verifying_key()
+                .to_encoded_point(false)
+                .as_bytes()
+                .to_vec(),
+            CryptoAlgorithm::EcdsaP384 => p384::ecdsa::SigningKey::from_slice(key)
+                .map_err(|_| CryptoError::InvalidKeyLength)?
+                .verifying_key()
+                .to_encoded_point(false)
+                .as_bytes()
+                .to_vec(),
+            CryptoAlgorithm::Ed25519 => self.ed25519_key()?.verifying_key().to_bytes().to_vec(),
+            CryptoAlgorithm::RSA => self.rsa_public_key()?,
+            _ => return Err(self.not_a_signer()),
+        };
+        Ok(PublicKey::new(self.algorithm.clone(), bytes))
     }
 
-    fn rsa_encrypt(&self, data: &[u8]) -> Result<Vec<u8>, CryptoError> {
-        // Implement RSA encryption logic using a suitable crate like `rsa`
-        // Placeholder implementation
-        Ok(data.to_vec())
+    fn ed25519_key(&self) -> Result<ed25519_dalek::SigningKey, CryptoError> {
+        let seed: zeroize::Zeroizing<[u8; 32]> = zeroize::Zeroizing::new(
+            self.key_material
+                .expose_secret()
+                .as_slice()
+                .try_into()
+                .map_err(|_| CryptoError::InvalidKeyLength)?,
+        );
+        Ok(ed25519_dalek::SigningKey::from_bytes(&seed))
     }
-    fn rsa_decrypt(&self, data: &[u8]) -> Result<Vec<u8>, CryptoError> {
-        // Implement RSA decryption logic
-        // Placeholder implementation
-        Ok(data.to_vec())
+
+    fn not_a_signer(&self) -> CryptoError {
+        CryptoError::CryptoError(format!("{:?} is not a signature algorithm", self.algorithm))
+    }
+
+    fn aead_cipher<C: KeyInit>(&self) -> Result<C, aead::Error> {
+        C::new_from_slice(self.key_material.expose_secret()).map_err(|_| aead::Error)
+    }
+
+    fn aead_encrypt<C: Aead + KeyInit>(&self, data: &[u8], aad: &[u8]) -> Result<Vec<u8>, aead::Error> {
+        let mut nonce = aead::Nonce::<C>::default();
+        getrandom::getrandom(&mut nonce).map_err(|_| aead::Error)?;
+        self.aead_seal::<C>(&nonce, data, aad)
+    }
+
+    /// Encrypt under a caller-chosen nonce; only known-answer tests use this.
+    fn aead_seal<C: Aead + KeyInit>(&self, nonce: &[u8], data: &[u8], aad: &[u8]) -> Result<Vec<u8>, aead::Error> {
+        let sealed = self
+            .aead_cipher::<C>()?
+            .encrypt(aead::Nonce::<C>::from_slice(nonce), Payload { msg: data, aad })?;
+
+        let mut out = Vec::with_capacity(nonce.len() + sealed.len());
+        out.extend_from_slice(nonce);
+        out.extend_from_slice(&sealed);
+        Ok(out)
+    }
+
+    fn aead_decrypt<C: Aead + KeyInit>(&self, data: &[u8], aad: &[u8]) -> Result<Vec<u8>, aead::Error> {
+        let nonce_len = aead::Nonce::<C>::default().len();
+        let tag_len = aead::Tag::<C>::default().len();
+        if data.len() < nonce_len + tag_len {
+            return Err(aead::Error);
+        }
+        let (nonce, sealed) = data.split_at(nonce_len);
+        self.aead_cipher::<C>()?
+            .decrypt(aead::Nonce::<C>::from_slice(nonce), Payload { msg: sealed, aad })
+    }
+
+}
+
+#[cfg(feature = "rsa")]
+impl CryptoPrimitive {
+    /// Parse the stored key; `RsaPrivateKey` wipes itself on drop.
+    fn rsa_key(&self) -> Result<RsaPrivateKey, CryptoError> {
+        rsa_private_key(self.key_material.expose_secret())
+    }
+
+    fn rsa_sign(&self, msg: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        use rsa::signature::{RandomizedSigner, SignatureEncoding};
+
+        Ok(rsa::pss::BlindedSigningKey::<Sha256>::new(self.rsa_key()?)
+            .try_sign_with_rng(&mut OsRng, msg)
+            .map_err(|_| CryptoError::SigningError)?
+            .to_vec())
+    }
+
+    fn rsa_public_key(&self) -> Result<Vec<u8>, CryptoError> {
+        Ok(self
+            .rsa_key()?
+            .to_public_key()
+            .to_pkcs1_der()
+            .map_err(|e| CryptoError::CryptoError(format!("RSA key encoding failed: {e}")))?
+            .into_vec())
+    }
+
+    fn rsa_encrypt(&self, data: &[u8], aad: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if !aad.is_empty() {
+            return Err(CryptoError::CryptoError("RSA-OAEP does not take associated data".to_string()));
+        }
+        self.rsa_key()?
+            .to_public_key()
+            .encrypt(&mut OsRng, Oaep::new::<Sha256>(), data)
+            .map_err(|_| CryptoError::RSAEncryptionError)
+    }
+
+    fn rsa_decrypt(&self, data: &[u8], aad: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if !aad.is_empty() {
+            return Err(CryptoError::CryptoError("RSA-OAEP does not take associated data".to_string()));
+        }
+        self.rsa_key()?
+            .decrypt_blinded(&mut OsRng, Oaep::new::<Sha256>(), data)
+            .map_err(|_| CryptoError::RSADecryptionError)
+    }
+}
+
+/// Without the `rsa` feature every RSA operation is refused.
+#[cfg(not(feature = "rsa"))]
+impl CryptoPrimitive {
+    fn rsa_sign(&self, _msg: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        Err(CryptoError::RSADisabled)
+    }
+
+    fn rsa_public_key(&self) -> Result<Vec<u8>, CryptoError> {
+        Err(CryptoError::RSADisabled)
+    }
+
+    fn rsa_encrypt(&self, _data: &[u8], _aad: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        Err(CryptoError::RSADisabled)
+    }
+
+    fn rsa_decrypt(&self, _data: &[u8], _aad: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        Err(CryptoError::RSADisabled)
+    }
+}
+
+#[cfg(feature = "rsa")]
+fn rsa_der_key_length(der: &[u8]) -> Result<KeyLength, CryptoError> {
+    rsa_private_key(der).and_then(|key| rsa_key_length(&key))
+}
+
+#[cfg(not(feature = "rsa"))]
+fn rsa_der_key_length(_der: &[u8]) -> Result<KeyLength, CryptoError> {
+    Err(CryptoError::RSADisabled)
+}
+
+#[cfg(feature = "rsa")]
+fn rsa_private_key(der: &[u8]) -> Result<RsaPrivateKey, CryptoError> {
+    RsaPrivateKey::from_pkcs1_der(der)
+        .map_err(|_| CryptoError::CryptoError("Invalid RSA private key".to_string()))
+}
+
+#[cfg(feature = "rsa")]
+fn rsa_key_length(key: &RsaPrivateKey) -> Result<KeyLength, CryptoError> {
+    use rsa::traits::PublicKeyParts;
+
+    match key.size() * 8 {
+        2048 => Ok(KeyLength::Bits2048),
+        3072 => Ok(KeyLength::Bits3072),
+        4096 => Ok(KeyLength::Bits4096),
+        _ => Err(CryptoError::InvalidKeyLength),
     }
 }
 
@@ -172,17 +482,307 @@ mod tests {
         assert_eq!(decrypted, plaintext);
     }
 
+    fn unhex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    /// Checks a known-answer vector: `ciphertext || tag` after the nonce.
+    fn check_aes_gcm_vector(key: &str, iv: &str, pt: &str, aad: &str, ct: &str, tag: &str) {
+        let primitive = CryptoPrimitive::new(&CryptoAlgorithm::AES, unhex(key), true).unwrap();
+        let nonce: [u8; AES_GCM_NONCE_LEN] = unhex(iv).try_into().unwrap();
+
+        let sealed = primitive.aead_seal::<Aes256Gcm>(&nonce, &unhex(pt), &unhex(aad)).unwrap();
+        assert_eq!(&sealed[..AES_GCM_NONCE_LEN], &nonce);
+        assert_eq!(sealed[AES_GCM_NONCE_LEN..], [unhex(ct), unhex(tag)].concat());
+        assert_eq!(primitive.decrypt_with_aad(&sealed, &unhex(aad)).unwrap(), unhex(pt));
+    }
+
+    #[test]
+    fn aes_gcm_matches_gcm_spec_vectors() {
+        let zero_key = "0000000000000000000000000000000000000000000000000000000000000000";
+        let zero_iv = "000000000000000000000000";
+        // Test cases 13, 14 and 16 (AES-256) from McGrew and Viega's GCM spec.
+        check_aes_gcm_vector(zero_key, zero_iv, "", "", "", "530f8afbc74536b9a963b4f1c4cb738b");
+        check_aes_gcm_vector(
+            zero_key,
+            zero_iv,
+            "00000000000000000000000000000000",
+            "",
+            "cea7403d4d606b6e074ec5d3baf39d18",
+            "d0d1c8a799996bf0265b98b5d48ab919",
+        );
+        check_aes_gcm_vector(
+            "feffe9928665731c6d6a8f9467308308feffe9928665731c6d6a8f9467308308",
+            "cafebabefacedbaddecaf888",
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72\
+             1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b39",
+            "feedfacedeadbeeffeedfacedeadbeefabaddad2",
+            "522dc1f099567d07f47f37a32a84427d643a8cdcbfe5c0c97598a2bd2555d1aa\
+             8cb08e48590dbb3da7b08b1056828838c5f61e6393ba7a0abcc9f662",
+            "76fc6ece0f4e1768cddf8853bb2d551b",
+        );
+    }
+
+    #[test]
+    fn aes_gcm_matches_nist_cavp_vectors() {
+        // gcmEncryptExtIV256.rsp, Keylen = 256, IVlen = 96, Taglen = 128.
+        check_aes_gcm_vector(
+            "b52c505a37d78eda5dd34f20c22540ea1b58963cf8e5bf8ffa85f9f2492505b4",
+            "516c33929df5a3284ff463d7",
+            "",
+            "",
+            "",
+            "bdc1ac884d332457a1d2664f168c76f0",
+        );
+        check_aes_gcm_vector(
+            "31bdadd96698c204aa9ce1448ea94ae1fb4a9a0b3c9d773b51bb1822666b8f22",
+            "0d18e06c7c725ac9e362e1ce",
+            "2db5168e932556f8089a0622981d017d",
+            "",
+            "fa4362189661d163fcd6a56d8bf0405a",
+            "d636ac1bbedd5cc3ee727dc2ab4a9489",
+        );
+        check_aes_gcm_vector(
+            "78dc4e0aaf52d935c3c01eea57428f00ca1fd475f5da86a49c8dd73d68c8e223",
+            "d79cf22d504cc793c3fb6c8a",
+            "",
+            "b96baa8c1c75a671bfb2d08d06be5f36",
+            "",
+            "3e5d486aa2e30b22e040b85723a06e76",
+        );
+        check_aes_gcm_vector(
+            "92e11dcdaa866f5ce790fd24501f92509aacf4cb8b1339d50c9c1240935dd08b",
+            "ac93a1a6145299bde902f21a",
+            "2d71bcfa914e4ac045b2aa60955fad24",
+            "1e0889016f67601c8ebea4943bc23ad6",
+            "8995ae2e6df3dbf96fac7b7137bae67f",
+            "eca5aa77d51d4a0a14d9c51e1da474ab",
+        );
+    }
+
     #[test]
-    fn rsa_encrypt_decrypt_is_passthrough() {
-        let key = vec![2u8; 256];
-        let primitive = CryptoPrimitive::new(&CryptoAlgorithm::RSA, key, false).unwrap();
+    fn aes_gcm_decrypt_accepts_and_rejects_cavp_vectors() {
+        // gcmDecrypt256.rsp, Keylen = 256, IVlen = 96, PTlen = 0, AADlen = 0, Taglen = 128.
+        let key = "f5a2b27c74355872eb3ef6c5feafaa740e6ae990d9d48c3bd9bb8235e589f010";
+        let iv = "58d2240f580a31c1d24948e9";
+        let tag = "15e051a5e4a5f5da6cea92e2ebee5bac";
+        let primitive = CryptoPrimitive::new(&CryptoAlgorithm::AES, unhex(key), true).unwrap();
+
+        let sealed = [unhex(iv), unhex(tag)].concat();
+        assert_eq!(primitive.decrypt(&sealed).unwrap(), Vec::<u8>::new());
+
+        // The same entry with its last tag bit flipped must FAIL.
+        let mut forged = sealed;
+        *forged.last_mut().unwrap() ^= 1;
+        assert!(primitive.decrypt(&forged).is_err());
+    }
+
+    #[test]
+    fn aes_gcm_uses_fresh_nonces() {
+        let primitive = CryptoPrimitive::new(&CryptoAlgorithm::AES, vec![4u8; 32], true).unwrap();
+        let a = primitive.encrypt(b"same message").unwrap();
+        let b = primitive.encrypt(b"same message").unwrap();
+
+        assert_eq!(a.len(), AES_GCM_NONCE_LEN + 12 + AES_GCM_TAG_LEN);
+        assert_ne!(a[..AES_GCM_NONCE_LEN], b[..AES_GCM_NONCE_LEN]);
+    }
+
+    #[test]
+    fn aes_gcm_rejects_tampering_and_wrong_aad() {
+        let primitive = CryptoPrimitive::new(&CryptoAlgorithm::AES, vec![5u8; 32], true).unwrap();
+        let sealed = primitive.encrypt_with_aad(b"wire transfer", b"account=42").unwrap();
+
+        let mut tampered = sealed.clone();
+        tampered[AES_GCM_NONCE_LEN] ^= 1;
+        assert!(matches!(
+            primitive.decrypt_with_aad(&tampered, b"account=42"),
+            Err(CryptoError::AESDecryptionError)
+        ));
+        assert!(matches!(
+            primitive.decrypt_with_aad(&sealed, b"account=43"),
+            Err(CryptoError::AESDecryptionError)
+        ));
+        assert!(matches!(primitive.decrypt(&sealed[..20]), Err(CryptoError::AESDecryptionError)));
+
+        let other = CryptoPrimitive::new(&CryptoAlgorithm::AES, vec![6u8; 32], true).unwrap();
+        assert!(matches!(other.decrypt_with_aad(&sealed, b"account=42"), Err(CryptoError::AESDecryptionError)));
+    }
+
+    #[test]
+    fn chacha20_poly1305_matches_rfc8439_vector() {
+        // RFC 8439 §2.8.2.
+        let key: Vec<u8> = (0x80..=0x9f).collect();
+        let nonce = unhex("070000004041424344454647");
+        let aad = unhex("50515253c0c1c2c3c4c5c6c7");
+        let plaintext = b"Ladies and Gentlemen of the class of '99: If I could offer you only one \
+                          tip for the future, sunscreen would be it.";
+        let expected = unhex(
+            "d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d6\
+             3dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b36\
+             92ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc\
+             3ff4def08e4b7a9de576d26586cec64b6116\
+             1ae10b594f09e26a7e902ecbd0600691",
+        );
+
+        let primitive = CryptoPrimitive::new(&CryptoAlgorithm::ChaCha20Poly1305, key, true).unwrap();
+        let sealed = primitive.aead_seal::<ChaCha20Poly1305>(&nonce, plaintext, &aad).unwrap();
+        assert_eq!(sealed[nonce.len()..], expected);
+        assert_eq!(primitive.decrypt_with_aad(&sealed, &aad).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn chacha_variants_round_trip_and_reject_tampering() {
+        for (algorithm, nonce_len) in [
+            (CryptoAlgorithm::ChaCha20Poly1305, AES_GCM_NONCE_LEN),
+            (CryptoAlgorithm::XChaCha20Poly1305, XCHACHA_NONCE_LEN),
+        ] {
+            let primitive = CryptoPrimitive::new(&algorithm, vec![9u8; 32], true).unwrap();
+            let sealed = primitive.encrypt_with_aad(b"no AES-NI here", b"host=arm").unwrap();
+            assert_eq!(sealed.len(), nonce_len + 14 + 16);
+            assert_eq!(primitive.decrypt_with_aad(&sealed, b"host=arm").unwrap(), b"no AES-NI here");
+
+            let mut tampered = sealed.clone();
+            *tampered.last_mut().unwrap() ^= 1;
+            assert!(matches!(primitive.decrypt_with_aad(&tampered, b"host=arm"), Err(CryptoError::DecryptionError)));
+            assert!(matches!(primitive.decrypt_with_aad(&sealed, b""), Err(CryptoError::DecryptionError)));
+        }
+    }
+
+    #[test]
+    fn ecdsa_p256_matches_rfc6979_vector() {
+        // RFC 6979 §A.2.5, message "sample" with SHA-256.
+        let key = unhex("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721");
+        let primitive = CryptoPrimitive::new(&CryptoAlgorithm::ECDSA, key, true).unwrap();
+
+        let public = primitive.public_key().unwrap();
+        assert_eq!(
+            public.as_bytes(),
+            unhex(
+                "0460fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6\
+                 7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299"
+            )
+        );
+
+        let signature = primitive.sign(b"sample").unwrap();
+        assert_eq!(
+            signature.as_bytes(),
+            unhex(
+                "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716\
+                 f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"
+            )
+        );
+        assert!(public.verify(b"sample", &signature).is_ok());
+    }
+
+    #[test]
+    fn ed25519_matches_rfc8032_vector() {
+        // RFC 8032 §7.1, TEST 1 (empty message).
+        let seed = unhex("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60");
+        let primitive = CryptoPrimitive::new(&CryptoAlgorithm::Ed25519, seed, true).unwrap();
+
+        assert_eq!(
+            primitive.public_key().unwrap().as_bytes(),
+            unhex("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+        );
+        let signature = primitive.sign(b"").unwrap();
+        assert_eq!(
+            signature.as_bytes(),
+            unhex(
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155\
+                 5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+            )
+        );
+        assert!(primitive.verify(b"", &signature).is_ok());
+    }
+
+    #[test]
+    fn signatures_reject_tampering_and_wrong_keys() {
+        for (algorithm, len) in [
+            (CryptoAlgorithm::ECDSA, 32),
+            (CryptoAlgorithm::EcdsaP384, 48),
+            (CryptoAlgorithm::Ed25519, 32),
+        ] {
+            let signer = CryptoPrimitive::new(&algorithm, vec![7u8; len], true).unwrap();
+            let other = CryptoPrimitive::new(&algorithm, vec![8u8; len], true).unwrap();
+            let signature = signer.sign(b"release v1.2.3").unwrap();
+
+            assert!(signer.verify(b"release v1.2.3", &signature).is_ok());
+            assert!(matches!(signer.verify(b"release v1.2.4", &signature), Err(CryptoError::InvalidSignature)));
+            assert!(matches!(other.verify(b"release v1.2.3", &signature), Err(CryptoError::InvalidSignature)));
+        }
+
+        let aes = CryptoPrimitive::new(&CryptoAlgorithm::AES, vec![1u8; 32], true).unwrap();
+        assert!(aes.sign(b"msg").is_err());
+    }
+
+    /// One 2048-bit key for the whole module; generation dominates runtime.
+    #[cfg(feature = "rsa")]
+    fn rsa_test_key() -> CryptoPrimitive {
+        static DER: std::sync::OnceLock<Vec<u8>> = std::sync::OnceLock::new();
+        let der = DER.get_or_init(|| {
+            CryptoPrimitive::generate_rsa(&KeyLength::Bits2048, false)
+                .unwrap()
+                .key_material
+                .expose_secret()
+                .clone()
+        });
+        CryptoPrimitive::new(&CryptoAlgorithm::RSA, der.clone(), true).unwrap()
+    }
+
+    #[cfg(feature = "rsa")]
+    #[test]
+    fn rsa_oaep_round_trips_and_rejects_tampering() {
+        let primitive = rsa_test_key();
+        assert_eq!(primitive.key_length, KeyLength::Bits2048);
 
         let data = b"just testing";
         let enc = primitive.encrypt(data).unwrap();
-        assert_eq!(enc, data);
+        assert_eq!(enc.len(), 256);
+        assert_ne!(enc, primitive.encrypt(data).unwrap());
+        assert_eq!(primitive.decrypt(&enc).unwrap(), data);
+
+        let mut tampered = enc.clone();
+        tampered[100] ^= 1;
+        assert!(matches!(primitive.decrypt(&tampered), Err(CryptoError::RSADecryptionError)));
+        assert!(matches!(primitive.encrypt(&[0u8; 191]), Err(CryptoError::RSAEncryptionError)));
+        assert!(primitive.encrypt_with_aad(data, b"label").is_err());
+    }
 
-        let dec = primitive.decrypt(&enc).unwrap();
-        assert_eq!(dec, data);
+    #[cfg(feature = "rsa")]
+    #[test]
+    fn rsa_pss_signs_and_verifies() {
+        let signer = rsa_test_key();
+        let signature = signer.sign(b"release v1.2.3").unwrap();
+        assert_eq!(signature.as_bytes().len(), 256);
+
+        let public = signer.public_key().unwrap();
+        assert!(public.verify(b"release v1.2.3", &signature).is_ok());
+        assert!(matches!(public.verify(b"release v1.2.4", &signature), Err(CryptoError::InvalidSignature)));
+    }
+
+    #[test]
+    fn rsa_rejects_malformed_and_short_keys() {
+        assert!(CryptoPrimitive::new(&CryptoAlgorithm::RSA, vec![2u8; 256], false).is_err());
+        assert!(matches!(
+            CryptoPrimitive::generate_rsa(&KeyLength::Bits1024, false),
+            Err(CryptoError::InvalidKeyLength)
+        ));
+    }
+
+    #[cfg(not(feature = "rsa"))]
+    #[test]
+    fn rsa_is_refused_without_the_feature() {
+        assert!(matches!(
+            CryptoPrimitive::generate_rsa(&KeyLength::Bits2048, false),
+            Err(CryptoError::RSADisabled)
+        ));
+        assert!(matches!(
+            CryptoPrimitive::new(&CryptoAlgorithm::RSA, vec![2u8; 256], false),
+            Err(CryptoError::RSADisabled)
+        ));
     }
 
     #[test]
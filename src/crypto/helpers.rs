@@ -53,11 +53,18 @@ pub fn parse_algorithm(
     alg: &Option<String>
 ) -> Result<CryptoAlgorithm, SecureError> {
     match alg.as_deref() {
-        Some("AES-256") => Ok(CryptoAlgorithm::AES),
-        Some("RSA") => Ok(CryptoAlgorithm::RSA),
+        Some("AES" | "AES-256") => Ok(CryptoAlgorithm::AES),
+        Some("RSA" | "RSA-3072" | "RSA-4096") => Ok(CryptoAlgorithm::RSA),
         Some("ECDSA") => Ok(CryptoAlgorithm::ECDSA),
-        _ => Err(SecureError::PipelineError(
-            "Unsupported algorithm".into()
+        Some("ECDSA-P384") => Ok(CryptoAlgorithm::EcdsaP384),
+        Some("Ed25519") => Ok(CryptoAlgorithm::Ed25519),
+        Some("ChaCha20-Poly1305") => Ok(CryptoAlgorithm::ChaCha20Poly1305),
+        Some("XChaCha20-Poly1305") => Ok(CryptoAlgorithm::XChaCha20Poly1305),
+        Some(other) => Err(SecureError::PipelineError(
+            format!("Unsupported algorithm: {}", other)
+        )),
+        None => Err(SecureError::PipelineError(
+            "Missing algorithm".into()
         )),
     }
 }
@@ -66,9 +73,14 @@ pub fn parse_key_length(
     alg: &Option<String>
 ) -> Result<KeyLength, SecureError> {
     match alg.as_deref() {
-        Some("AES-256") => Ok(KeyLength::Bits256),
+        Some("AES" | "AES-256") => Ok(KeyLength::Bits256),
         Some("RSA") => Ok(KeyLength::Bits2048),
+        Some("RSA-3072") => Ok(KeyLength::Bits3072),
+        Some("RSA-4096") => Ok(KeyLength::Bits4096),
         Some("ECDSA") => Ok(KeyLength::Bits256),
+        Some("ECDSA-P384") => Ok(KeyLength::Bits384),
+        Some("Ed25519") => Ok(KeyLength::Bits256),
+        Some("ChaCha20-Poly1305" | "XChaCha20-Poly1305") => Ok(KeyLength::Bits256),
         _ => Err(SecureError::PipelineError(
             "Unsupported algorithm for key length".into()
         )),
@@ -88,6 +100,10 @@ mod tests {
             parse_algorithm(&Some("AES-256".into())).unwrap(),
             CryptoAlgorithm::AES
         );
+        assert_eq!(
+            parse_algorithm(&Some("AES".into())).unwrap(),
+            CryptoAlgorithm::AES
+        );
         assert_eq!(
             parse_algorithm(&Some("RSA".into())).unwrap(),
             CryptoAlgorithm::RSA
@@ -96,6 +112,14 @@ mod tests {
             parse_algorithm(&Some("ECDSA".into())).unwrap(),
             CryptoAlgorithm::ECDSA
         );
+        assert_eq!(
+            parse_algorithm(&Some("ChaCha20-Poly1305".into())).unwrap(),
+            CryptoAlgorithm::ChaCha20Poly1305
+        );
+        assert_eq!(
+            parse_algorithm(&Some("XChaCha20-Poly1305".into())).unwrap(),
+            CryptoAlgorithm::XChaCha20Poly1305
+        );
     }
 
     #[test]
@@ -121,6 +145,18 @@ mod tests {
             parse_key_length(&Some("ECDSA".into())).unwrap(),
             KeyLength::Bits256
         );
+        assert_eq!(
+            parse_key_length(&Some("XChaCha20-Poly1305".into())).unwrap(),
+            KeyLength::Bits256
+        );
+        assert_eq!(
+            parse_key_length(&Some("ECDSA-P384".into())).unwrap(),
+            KeyLength::Bits384
+        );
+        assert_eq!(
+            parse_key_length(&Some("RSA-4096".into())).unwrap(),
+            KeyLength::Bits4096
+        );
     }
 
     #[test]
@@ -0,0 +1,178 @@
+//! ----------------------------------------------
+//! DOCUMENT DETAILS -----------------------------
+//!
+//! filename:key_generator.rs
+//! description:OS CSPRNG key generation for every CryptoAlgorithm
+//! usages:[crypto_builder.rs][key_manager.rs]
+//!
+//! ----------------------------------------------
+//! FEATURE NOTES --------------------------------
+//!
+//! feature_name:async
+//! deps:[tokio][async_trait]
+//! scope:[struct KeyGenerator]
+//! corpus:true
+//!
+//! feature_name:std
+//! deps:[std][getrandom]
+//! scope:[struct KeyGenerator]
+//! corpus:false
+//!
+//! ----------------------------------------------
+//! CORPUS FEATURES ------------------------------
+//!
+#![cfg(feature = "async")]
+
+use secrecy::{ExposeSecretMut, SecretBox};
+
+use super::{
+    crypto_algorithm::CryptoAlgorithm,
+    crypto_primitive::CryptoPrimitive,
+    key_length::KeyLength,
+};
+use crate::{classified_data::ClassifiedData, errors::CryptoError};
+
+/// Attempts at drawing an in-range ECDSA scalar before giving up. A P-256
+/// draw is rejected with probability below 2^-32, so hitting this means the
+/// RNG is broken.
+const MAX_SCALAR_ATTEMPTS: usize = 8;
+
+/// Draws keys from the OS CSPRNG (`getrandom`).
+///
+/// Random bytes are written straight into the classified buffer that will
+/// hold them; no plain copy is made along the way.
+///
+/// # Example
+/// ```
+/// use classified::crypto::{
+///     crypto_algorithm::CryptoAlgorithm, key_generator::KeyGenerator, key_length::KeyLength,
+/// };
+///
+/// let key = KeyGenerator::generate(&CryptoAlgorithm::AES, &KeyLength::Bits256, true).unwrap();
+/// let sealed = key.encrypt(b"hello").unwrap();
+/// assert_eq!(key.decrypt(&sealed).unwrap(), b"hello");
+/// ```
+pub struct KeyGenerator;
+
+impl KeyGenerator {
+    /// `len` uniformly random bytes.
+    pub fn random_bytes(len: usize) -> Result<ClassifiedData<Vec<u8>>, CryptoError> {
+        let mut secret = ClassifiedData::new(vec![0u8; len]);
+        let mut filled = Ok(());
+        secret.update(|buf| filled = fill(buf));
+        filled.map(|()| secret)
+    }
+
+    /// A fresh key for `algorithm`, shaped as [`CryptoPrimitive::new`]
+    /// expects it.
+    ///
+    /// Fails with [`CryptoError::InvalidKeyLength`] if `key_length` does not
+    /// suit `algorithm`, and with [`CryptoError::KeyGenerationError`] if the
+    /// RNG fails.
+    pub fn generate(
+        algorithm: &CryptoAlgorithm,
+        key_length: &KeyLength,
+        zeroize: bool,
+    ) -> Result<CryptoPrimitive, CryptoError> {
+        key_length.check_for(algorithm)?;
+
+        let key_material = match algorithm {
+            CryptoAlgorithm::RSA => return CryptoPrimitive::generate_rsa(key_length, zeroize),
+            CryptoAlgorithm::ECDSA => {
+                random_scalar(key_length, |k| p256::SecretKey::from_slice(k).is_ok())?
+            }
+            CryptoAlgorithm::EcdsaP384 => {
+                random_scalar(key_length, |k| p384::SecretKey::from_slice(k).is_ok())?
+            }
+            CryptoAlgorithm::AES
+            | CryptoAlgorithm::Ed25519
+            | CryptoAlgorithm::ChaCha20Poly1305
+            | CryptoAlgorithm::XChaCha20Poly1305 => {
+                let mut key = SecretBox::new(Box::new(vec![0u8; key_length.as_bytes()]));
+                fill(key.expose_secret_mut())?;
+                key
+            }
+        };
+
+        Ok(CryptoPrimitive {
+            algorithm: algorithm.clone(),
+            key_length: key_length.clone(),
+            key_material,
+            zeroize,
+        })
+    }
+}
+
+fn fill(buf: &mut [u8]) -> Result<(), CryptoError> {
+    getrandom::getrandom(buf).map_err(|e| CryptoError::KeyGenerationError(e.to_string()))
+}
+
+/// Rejection-sample a scalar in `[1, n)`; `valid` checks the range.
+fn random_scalar(
+    key_length: &KeyLength,
+    valid: impl Fn(&[u8]) -> bool,
+) -> Result<SecretBox<Vec<u8>>, CryptoError> {
+    let mut key = SecretBox::new(Box::new(vec![0u8; key_length.as_bytes()]));
+    for _ in 0..MAX_SCALAR_ATTEMPTS {
+        fill(key.expose_secret_mut())?;
+        if valid(key.expose_secret_mut()) {
+            return Ok(key);
+        }
+    }
+    Err(CryptoError::KeyGenerationError("no valid scalar drawn".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generates_distinct_usable_keys_for_each_algorithm() {
+        for (algorithm, key_length) in [
+            (CryptoAlgorithm::AES, KeyLength::Bits256),
+            (CryptoAlgorithm::ChaCha20Poly1305, KeyLength::Bits256),
+            (CryptoAlgorithm::XChaCha20Poly1305, KeyLength::Bits256),
+            (CryptoAlgorithm::ECDSA, KeyLength::Bits256),
+            (CryptoAlgorithm::EcdsaP384, KeyLength::Bits384),
+            (CryptoAlgorithm::Ed25519, KeyLength::Bits256),
+        ] {
+            let a = KeyGenerator::generate(&algorithm, &key_length, true).unwrap();
+            let b = KeyGenerator::generate(&algorithm, &key_length, true).unwrap();
+            assert_eq!(a.key_length, key_length);
+
+            let key = secrecy::ExposeSecret::expose_secret(&a.key_material);
+            assert_eq!(key.len(), key_length.as_bytes());
+            assert_ne!(key, secrecy::ExposeSecret::expose_secret(&b.key_material));
+
+            // Each key must be accepted by its algorithm.
+            let usable = match algorithm {
+                CryptoAlgorithm::ECDSA | CryptoAlgorithm::EcdsaP384 | CryptoAlgorithm::Ed25519 => {
+                    a.sign(b"msg").map(drop)
+                }
+                _ => a.encrypt(b"msg").map(drop),
+            };
+            assert!(usable.is_ok(), "{algorithm:?}");
+        }
+    }
+
+    #[test]
+    fn rejects_mismatched_key_length() {
+        assert!(matches!(
+            KeyGenerator::generate(&CryptoAlgorithm::AES, &KeyLength::Bits512, true),
+            Err(CryptoError::InvalidKeyLength)
+        ));
+        assert!(matches!(
+            KeyGenerator::generate(&CryptoAlgorithm::RSA, &KeyLength::Bits1024, true),
+            Err(CryptoError::InvalidKeyLength)
+        ));
+    }
+
+    #[test]
+    fn random_bytes_fills_classified_storage() {
+        let a = KeyGenerator::random_bytes(64).unwrap();
+        let b = KeyGenerator::random_bytes(64).unwrap();
+        assert_eq!(a.expose().len(), 64);
+        assert_ne!(a.expose(), b.expose());
+        assert!(KeyGenerator::random_bytes(0).unwrap().expose().is_empty());
+    }
+}
@@ -39,13 +39,18 @@
 //! 
 //! 
 
+use super::crypto_algorithm::CryptoAlgorithm;
+use crate::errors::CryptoError;
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum KeyLength {
     Bits256,
+    Bits384,
     Bits512,
     Bits1024,
     #[default]
     Bits2048,
+    Bits3072,
     Bits4096,
 }
 
@@ -54,9 +59,53 @@ impl KeyLength {
         match self {
             KeyLength::Bits1024 => 1024 / 8,
             KeyLength::Bits2048 => 2048 / 8,
+            KeyLength::Bits3072 => 3072 / 8,
             KeyLength::Bits4096 => 4096 / 8,
             KeyLength::Bits256 => 256 / 8,
+            KeyLength::Bits384 => 384 / 8,
             KeyLength::Bits512 => 512 / 8,
         }
     }
+
+    pub fn as_bits(&self) -> usize {
+        self.as_bytes() * 8
+    }
+
+    /// Fails with [`CryptoError::InvalidKeyLength`] unless `algorithm` can use
+    /// a key of this length: 2048, 3072 or 4096 bits for RSA, 384 for
+    /// ECDSA P-384 and 256 for everything else.
+    pub fn check_for(&self, algorithm: &CryptoAlgorithm) -> Result<(), CryptoError> {
+        let valid = match algorithm {
+            CryptoAlgorithm::RSA => {
+                matches!(self, KeyLength::Bits2048 | KeyLength::Bits3072 | KeyLength::Bits4096)
+            }
+            CryptoAlgorithm::EcdsaP384 => *self == KeyLength::Bits384,
+            CryptoAlgorithm::AES
+            | CryptoAlgorithm::ECDSA
+            | CryptoAlgorithm::Ed25519
+            | CryptoAlgorithm::ChaCha20Poly1305
+            | CryptoAlgorithm::XChaCha20Poly1305 => *self == KeyLength::Bits256,
+        };
+        if valid { Ok(()) } else { Err(CryptoError::InvalidKeyLength) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_lengths_per_algorithm() {
+        for len in [KeyLength::Bits2048, KeyLength::Bits3072, KeyLength::Bits4096] {
+            assert!(len.check_for(&CryptoAlgorithm::RSA).is_ok());
+        }
+        assert!(KeyLength::Bits1024.check_for(&CryptoAlgorithm::RSA).is_err());
+        assert!(KeyLength::Bits256.check_for(&CryptoAlgorithm::RSA).is_err());
+
+        assert!(KeyLength::Bits384.check_for(&CryptoAlgorithm::EcdsaP384).is_ok());
+        assert!(KeyLength::Bits256.check_for(&CryptoAlgorithm::EcdsaP384).is_err());
+        assert!(KeyLength::Bits256.check_for(&CryptoAlgorithm::AES).is_ok());
+        assert!(KeyLength::Bits2048.check_for(&CryptoAlgorithm::AES).is_err());
+        assert_eq!(KeyLength::Bits3072.as_bits(), 3072);
+    }
 }
@@ -0,0 +1,146 @@
+//! ----------------------------------------------
+//! DOCUMENT DETAILS -----------------------------
+//!
+//! filename:signature.rs
+//! description:signatures and public keys for ECDSA, Ed25519 and RSA-PSS
+//! usages:[crypto_primitive.rs][signing_stage.rs][traits.rs]
+//!
+//! ----------------------------------------------
+//! FEATURE NOTES --------------------------------
+//!
+//! feature_name:async
+//! deps:[tokio][async_trait]
+//! scope:[struct Signature][struct PublicKey]
+//! corpus:true
+//!
+//! feature_name:std
+//! deps:[std][p256][p384][ed25519-dalek]
+//! scope:[struct Signature][struct PublicKey]
+//! corpus:false
+//!
+//! feature_name:rsa
+//! deps:[rsa]
+//! scope:[fn verify_rsa_pss]
+//! corpus:false
+//!
+//! ----------------------------------------------
+//! CORPUS FEATURES ------------------------------
+//!
+#![cfg(feature = "async")]
+
+//! Signatures and public keys.
+//!
+//! Both are public values: they are plain types, not classified wrappers,
+//! and can be logged, serialized and shipped to verifiers freely.
+//!
+//! | algorithm                   | signature                 | public key                  |
+//! |-----------------------------|---------------------------|-----------------------------|
+//! | `ECDSA` (P-256, SHA-256)    | 64 bytes, `r \|\| s`      | 65 bytes, uncompressed SEC1 |
+//! | `EcdsaP384` (P-384, SHA-384)| 96 bytes, `r \|\| s`      | 97 bytes, uncompressed SEC1 |
+//! | `Ed25519`                   | 64 bytes (RFC 8032)       | 32 bytes                    |
+//! | `RSA` (PSS, SHA-256)        | modulus size              | PKCS#1 DER                  |
+//!
+//! RSA needs the `rsa` feature; without it RSA verification fails with
+//! [`CryptoError::RSADisabled`].
+
+use p256::ecdsa::signature::Verifier as _;
+#[cfg(feature = "rsa")]
+use rsa::pkcs1::DecodeRsaPublicKey;
+
+use super::crypto_algorithm::CryptoAlgorithm;
+use crate::errors::CryptoError;
+
+/// A signature together with the algorithm that produced it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    algorithm: CryptoAlgorithm,
+    bytes: Vec<u8>,
+}
+
+impl Signature {
+    pub fn new(algorithm: CryptoAlgorithm, bytes: Vec<u8>) -> Self {
+        Self { algorithm, bytes }
+    }
+
+    pub fn algorithm(&self) -> &CryptoAlgorithm {
+        &self.algorithm
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// A public verification key, derived from a signing
+/// [`CryptoPrimitive`](super::crypto_primitive::CryptoPrimitive).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicKey {
+    algorithm: CryptoAlgorithm,
+    bytes: Vec<u8>,
+}
+
+impl PublicKey {
+    /// Wrap an encoded public key received from elsewhere.
+    pub fn new(algorithm: CryptoAlgorithm, bytes: Vec<u8>) -> Self {
+        Self { algorithm, bytes }
+    }
+
+    pub fn algorithm(&self) -> &CryptoAlgorithm {
+        &self.algorithm
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Check `signature` over `msg`.
+    ///
+    /// Fails with [`CryptoError::InvalidSignature`] if the signature does not
+    /// verify, is malformed, or was made with a different algorithm.
+    pub fn verify(&self, msg: &[u8], signature: &Signature) -> Result<(), CryptoError> {
+        if signature.algorithm != self.algorithm {
+            return Err(CryptoError::InvalidSignature);
+        }
+        let valid = match self.algorithm {
+            CryptoAlgorithm::ECDSA => verify_p256(&self.bytes, msg, &signature.bytes),
+            CryptoAlgorithm::EcdsaP384 => verify_p384(&self.bytes, msg, &signature.bytes),
+            CryptoAlgorithm::Ed25519 => verify_ed25519(&self.bytes, msg, &signature.bytes),
+            #[cfg(feature = "rsa")]
+            CryptoAlgorithm::RSA => verify_rsa_pss(&self.bytes, msg, &signature.bytes),
+            #[cfg(not(feature = "rsa"))]
+            CryptoAlgorithm::RSA => return Err(CryptoError::RSADisabled),
+            _ => {
+                return Err(CryptoError::CryptoError(format!(
+                    "{:?} is not a signature algorithm",
+                    self.algorithm
+                )))
+            }
+        };
+        valid.ok_or(CryptoError::InvalidSignature)
+    }
+}
+
+fn verify_p256(key: &[u8], msg: &[u8], sig: &[u8]) -> Option<()> {
+    let key = p256::ecdsa::VerifyingKey::from_sec1_bytes(key).ok()?;
+    let sig = p256::ecdsa::Signature::from_slice(sig).ok()?;
+    key.verify(msg, &sig).ok()
+}
+
+fn verify_p384(key: &[u8], msg: &[u8], sig: &[u8]) -> Option<()> {
+    let key = p384::ecdsa::VerifyingKey::from_sec1_bytes(key).ok()?;
+    let sig = p384::ecdsa::Signature::from_slice(sig).ok()?;
+    key.verify(msg, &sig).ok()
+}
+
+fn verify_ed25519(key: &[u8], msg: &[u8], sig: &[u8]) -> Option<()> {
+    let key = ed25519_dalek::VerifyingKey::from_bytes(key.try_into().ok()?).ok()?;
+    let sig = ed25519_dalek::Signature::from_slice(sig).ok()?;
+    key.verify_strict(msg, &sig).ok()
+}
+
+#[cfg(feature = "rsa")]
+fn verify_rsa_pss(key: &[u8], msg: &[u8], sig: &[u8]) -> Option<()> {
+    let key = rsa::RsaPublicKey::from_pkcs1_der(key).ok()?;
+    let sig = rsa::pss::Signature::try_from(sig).ok()?;
+    rsa::pss::VerifyingKey::<sha2::Sha256>::new(key).verify(msg, &sig).ok()
+}
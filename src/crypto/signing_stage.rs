@@ -0,0 +1,158 @@
+//! ----------------------------------------------
+//! DOCUMENT DETAILS -----------------------------
+//!
+//! filename:signing_stage.rs
+//! description:layer that signs frames and records the signature in metadata
+//! usages:none in crate yet
+//!
+//! ----------------------------------------------
+//! FEATURE NOTES --------------------------------
+//!
+//! feature_name:async
+//! deps:[tokio][async_trait]
+//! scope:[impl FramedLayerHandler for SigningStage]
+//! corpus:true
+//!
+//! feature_name:logging
+//! deps:[tracing]
+//! scope:[fn SigningStage::handle]
+//! corpus:false
+//!
+//! ----------------------------------------------
+//! CORPUS FEATURES ------------------------------
+//!
+#![cfg(feature = "async")]
+
+use async_trait::async_trait;
+
+#[cfg(feature = "logging")]
+use tracing::debug;
+
+use super::{
+    crypto_primitive::CryptoPrimitive,
+    signature::PublicKey,
+};
+use crate::{
+    classification::Level,
+    errors::{CryptoError, SecureError},
+    traits::{Frame, FramedLayerHandler},
+};
+
+/// Signs each frame's data and appends the signature to
+/// `frame.metadata.signatures`, leaving the data untouched.
+///
+/// # Example
+/// ```
+/// # tokio_test_block_on(async {
+/// use classified::classified_data::ClassifiedData;
+/// use classified::crypto::{
+///     crypto_algorithm::CryptoAlgorithm, crypto_primitive::CryptoPrimitive,
+///     signing_stage::{verify_frame, SigningStage},
+/// };
+/// use classified::traits::{Frame, FramedLayerHandler};
+///
+/// let signer = CryptoPrimitive::new(&CryptoAlgorithm::Ed25519, vec![7u8; 32], true).unwrap();
+/// let stage = SigningStage::new(signer).unwrap();
+/// let public = stage.public_key().clone();
+///
+/// let frame = stage.handle(Frame::new(ClassifiedData::new(b"payload".to_vec()))).await.unwrap();
+/// assert!(verify_frame(&public, &frame).is_ok());
+/// # });
+/// # fn tokio_test_block_on<F: std::future::Future>(f: F) -> F::Output {
+/// #     tokio::runtime::Runtime::new().unwrap().block_on(f)
+/// # }
+/// ```
+pub struct SigningStage {
+    signer: CryptoPrimitive,
+    public_key: PublicKey,
+}
+
+impl SigningStage {
+    /// Fails unless `signer` holds a valid ECDSA or Ed25519 private key.
+    pub fn new(signer: CryptoPrimitive) -> Result<Self, CryptoError> {
+        let public_key = signer.public_key()?;
+        Ok(Self { signer, public_key })
+    }
+
+    /// The key verifiers need to check this stage's signatures.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+}
+
+#[async_trait]
+impl<L: Level> FramedLayerHandler<L> for SigningStage {
+    async fn handle(&self, mut frame: Frame<L>) -> Result<Frame<L>, SecureError> {
+        #[cfg(feature = "logging")]
+        debug!(algorithm = ?self.public_key.algorithm(), "SigningStage signing frame.");
+
+        let signature = self
+            .signer
+            .sign(frame.data.expose())
+            .map_err(|e| SecureError::PipelineError(format!("Signing stage: {e}")))?;
+        frame.metadata.signatures.push(signature);
+        Ok(frame)
+    }
+}
+
+/// Check that `frame` carries a valid signature by `key` over its data.
+pub fn verify_frame<L: Level>(key: &PublicKey, frame: &Frame<L>) -> Result<(), CryptoError> {
+    let data = frame.data.expose();
+    frame
+        .metadata
+        .signatures
+        .iter()
+        .filter(|sig| sig.algorithm() == key.algorithm())
+        .find_map(|sig| key.verify(data, sig).ok())
+        .ok_or(CryptoError::InvalidSignature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        classified_data::ClassifiedData,
+        crypto::crypto_algorithm::CryptoAlgorithm,
+        layer_stack::LayerStack,
+    };
+
+    fn stage(algorithm: CryptoAlgorithm, len: usize, byte: u8) -> SigningStage {
+        SigningStage::new(CryptoPrimitive::new(&algorithm, vec![byte; len], true).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn stages_stack_signatures_in_metadata() {
+        let ecdsa = stage(CryptoAlgorithm::ECDSA, 32, 1);
+        let ed25519 = stage(CryptoAlgorithm::Ed25519, 32, 2);
+        let keys = [ecdsa.public_key().clone(), ed25519.public_key().clone()];
+
+        let mut stack = LayerStack::new();
+        stack.push(ecdsa);
+        stack.push(ed25519);
+        let frame = stack.run(Frame::new(ClassifiedData::new(b"audit log".to_vec()))).await.unwrap();
+
+        assert_eq!(frame.data.expose(), b"audit log");
+        assert_eq!(frame.metadata.signatures.len(), 2);
+        for key in &keys {
+            assert!(verify_frame(key, &frame).is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn tampered_frame_fails_verification() {
+        let signer = stage(CryptoAlgorithm::EcdsaP384, 48, 3);
+        let mut frame = signer.handle(Frame::new(ClassifiedData::new(b"v1".to_vec()))).await.unwrap();
+
+        frame.data = ClassifiedData::new(b"v2".to_vec());
+        assert!(matches!(verify_frame(signer.public_key(), &frame), Err(CryptoError::InvalidSignature)));
+
+        let stranger = stage(CryptoAlgorithm::EcdsaP384, 48, 4);
+        assert!(verify_frame(stranger.public_key(), &frame).is_err());
+    }
+
+    #[test]
+    fn rejects_non_signing_primitives() {
+        let aes = CryptoPrimitive::new(&CryptoAlgorithm::AES, vec![0u8; 32], true).unwrap();
+        assert!(SigningStage::new(aes).is_err());
+    }
+}
@@ -29,6 +29,7 @@
 #![allow(unused, type_alias_bounds)]
 
 use crate::classified_data::ClassifiedData;
+use crate::ephemeral_classified_data::EphemeralClassifiedData;
 use crate::errors::SecureError;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -76,6 +77,12 @@ where
         self.storage.read().await.get(key).cloned()
     }
 
+    /// Removes the entry stored under the given key and hands it out as a
+    /// burn-after-read value.
+    pub async fn take(&self, key: &str) -> Option<EphemeralClassifiedData<T>> {
+        self.storage.write().await.remove(key).map(Into::into)
+    }
+
     /// Removes the entry stored under the given key.
     ///
     /// If no entry exists, this is a no-op.
@@ -157,6 +164,18 @@ mod tests {
         assert!(after_remove.is_none());
     }
 
+    #[tokio::test]
+    async fn take_hands_out_value_once() {
+        let repo = DataRepository::<MockData>::new();
+        let value = MockData::from_str("bootstrap");
+        repo.insert("token".to_string(), value).await.unwrap();
+
+        let token = repo.take("token").await.unwrap();
+        assert!(repo.get("token").await.is_none());
+        assert_eq!(token.expose(|t| *t), Some(value));
+        assert!(token.is_burned());
+    }
+
     #[tokio::test]
     async fn remove_nonexistent_key_is_ok() {
         let repo = DataRepository::<MockData>::new();
@@ -0,0 +1,224 @@
+//! ----------------------------------------------
+//! DOCUMENT DETAILS -----------------------------
+//!
+//! filename:declassification.rs
+//! description:principals and audit records for declassification
+//! usages:[classified_data.rs][sized_classified_data.rs][exposure_aware_classified_data.rs]
+//!
+//! ----------------------------------------------
+//! FEATURE NOTES --------------------------------
+//!
+//! feature_name:logging
+//! deps:[tracing]
+//! scope:[fn audit]
+//! corpus:false
+//!
+//! feature_name:std
+//! deps:[std]
+//! scope:[struct Principal][trait DeclassificationSink]
+//! corpus:false
+//!
+//! ----------------------------------------------
+//! CORPUS FEATURES ------------------------------
+//!
+#![cfg(feature = "std")]
+
+//! Audited declassification.
+//!
+//! `declassify(self, reason, authorizer)` is the single way to turn a
+//! classified wrapper into a plain value, for example to publish a derived
+//! public key. It is implemented by
+//! [`ClassifiedData`](crate::classified_data::ClassifiedData),
+//! [`SizedClassifiedData`](crate::sized_classified_data::SizedClassifiedData) and
+//! [`ExposureAwareClassifiedData`](crate::exposure_aware_classified_data::ExposureAwareClassifiedData),
+//! and every call produces a [`DeclassificationRecord`] naming who released
+//! what, why, and from where.
+//!
+//! [`ClassifiedData::downgrade`](crate::classified_data::ClassifiedData::downgrade)
+//! keeps the value classified but lowers its level, so it is recorded through
+//! the same sink with [`DeclassificationRecord::downgraded_to`] set.
+
+use std::{
+    panic::Location,
+    sync::{Arc, RwLock},
+    time::SystemTime,
+};
+
+#[cfg(feature = "logging")]
+use tracing::warn;
+
+use crate::classification::{Clearance, Label};
+
+/// The person or service on whose authority a value is declassified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+    id: String,
+    clearance: Option<Clearance>,
+}
+
+impl Principal {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            clearance: None,
+        }
+    }
+
+    pub fn with_clearance(mut self, clearance: Clearance) -> Self {
+        self.clearance = Some(clearance);
+        self
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn clearance(&self) -> Option<&Clearance> {
+        self.clearance.as_ref()
+    }
+}
+
+/// A structured record of a single `declassify` or `downgrade` call.
+#[derive(Debug, Clone)]
+pub struct DeclassificationRecord {
+    /// Id of the authorizing [`Principal`].
+    pub principal: String,
+    /// The justification given by the caller.
+    pub reason: String,
+    /// Name of the wrapper the value was released from.
+    pub source: &'static str,
+    /// Label of the released value, when the wrapper carries one.
+    pub label: Option<Label>,
+    /// The level a downgraded value was moved to; `None` for a release.
+    pub downgraded_to: Option<&'static str>,
+    /// Where `declassify` or `downgrade` was called from.
+    pub location: &'static Location<'static>,
+    pub time: SystemTime,
+}
+
+/// Receives a [`DeclassificationRecord`] for every declassification.
+pub trait DeclassificationSink: Send + Sync {
+    fn record(&self, record: &DeclassificationRecord);
+}
+
+static DECLASSIFICATION_SINK: RwLock<Option<Arc<dyn DeclassificationSink>>> = RwLock::new(None);
+
+/// Installs the process-wide sink for declassification records.
+pub fn set_declassification_sink(sink: Arc<dyn DeclassificationSink>) {
+    *DECLASSIFICATION_SINK.write().unwrap_or_else(|e| e.into_inner()) = Some(sink);
+}
+
+/// Removes the sink installed by [`set_declassification_sink`].
+pub fn clear_declassification_sink() {
+    *DECLASSIFICATION_SINK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Builds, logs and dispatches the record for a declassification.
+///
+/// # Panics
+/// Panics if `reason` is blank; an unexplained release cannot be audited.
+#[track_caller]
+pub(crate) fn audit(source: &'static str, label: Option<Label>, reason: &str, authorizer: &Principal) {
+    dispatch(source, label, None, reason, authorizer);
+}
+
+/// Builds, logs and dispatches the record for a downgrade to level `to`.
+///
+/// # Panics
+/// Panics if `reason` is blank.
+#[cfg(feature = "async")]
+#[track_caller]
+pub(crate) fn audit_downgrade(
+    source: &'static str,
+    label: Label,
+    to: &'static str,
+    reason: &str,
+    authorizer: &Principal,
+) {
+    dispatch(source, Some(label), Some(to), reason, authorizer);
+}
+
+#[track_caller]
+fn dispatch(
+    source: &'static str,
+    label: Option<Label>,
+    downgraded_to: Option<&'static str>,
+    reason: &str,
+    authorizer: &Principal,
+) {
+    assert!(!reason.trim().is_empty(), "declassification requires a reason");
+
+    let record = DeclassificationRecord {
+        principal: authorizer.id.clone(),
+        reason: reason.to_owned(),
+        source,
+        label,
+        downgraded_to,
+        location: Location::caller(),
+        time: SystemTime::now(),
+    };
+
+    #[cfg(feature = "logging")]
+    warn!(
+        target: "security_audit",
+        principal = %record.principal,
+        reason = %record.reason,
+        source = record.source,
+        label = ?record.label.as_ref().map(ToString::to_string),
+        downgraded_to = ?record.downgraded_to,
+        location = %record.location,
+        "{}",
+        if record.downgraded_to.is_some() {
+            "Classified value downgraded"
+        } else {
+            "Classified value declassified"
+        }
+    );
+
+    let sink = DECLASSIFICATION_SINK.read().unwrap_or_else(|e| e.into_inner()).clone();
+    if let Some(sink) = sink {
+        sink.record(&record);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Collects records; tests filter by `reason` since the sink is global.
+    #[derive(Default)]
+    pub(crate) struct RecordingSink(pub(crate) Mutex<Vec<DeclassificationRecord>>);
+
+    impl DeclassificationSink for RecordingSink {
+        fn record(&self, record: &DeclassificationRecord) {
+            self.0.lock().unwrap().push(record.clone());
+        }
+    }
+
+    pub(crate) fn recording_sink() -> Arc<RecordingSink> {
+        static SINK: std::sync::OnceLock<Arc<RecordingSink>> = std::sync::OnceLock::new();
+        let sink = SINK.get_or_init(|| Arc::new(RecordingSink::default()));
+        set_declassification_sink(sink.clone());
+        sink.clone()
+    }
+
+    #[test]
+    fn audit_reaches_sink() {
+        let sink = recording_sink();
+        audit("Test", None, "unit test release", &Principal::new("alice"));
+
+        let records = sink.0.lock().unwrap();
+        let record = records.iter().find(|r| r.reason == "unit test release").unwrap();
+        assert_eq!(record.principal, "alice");
+        assert_eq!(record.source, "Test");
+        assert_eq!(record.downgraded_to, None);
+        assert!(record.location.file().ends_with("declassification.rs"));
+    }
+
+    #[test]
+    #[should_panic(expected = "declassification requires a reason")]
+    fn blank_reason_is_rejected() {
+        audit("Test", None, "  ", &Principal::new("alice"));
+    }
+}
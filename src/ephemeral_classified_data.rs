@@ -0,0 +1,221 @@
+//! ----------------------------------------------
+//! DOCUMENT DETAILS -----------------------------
+//!
+//! filename:ephemeral_classified_data.rs
+//! description:burn-after-read and time-to-live classified values
+//! usages:[classified_data.rs][data_repository.rs]
+//!
+//! ----------------------------------------------
+//! FEATURE NOTES --------------------------------
+//!
+//! feature_name:async
+//! deps:[tokio]
+//! scope:[fn EphemeralClassifiedData::arm_expiry][fn EphemeralClassifiedData::take_async]
+//! corpus:true
+//!
+//! feature_name:std
+//! deps:[std]
+//! scope:[struct EphemeralClassifiedData]
+//! corpus:false
+//!
+//! ----------------------------------------------
+//! CORPUS FEATURES ------------------------------
+//!
+#![cfg(feature = "async")]
+#![cfg(feature = "std")]
+
+//! One-time secrets such as bootstrap credentials and enrollment tokens.
+//!
+//! An [`EphemeralClassifiedData`] hands its value out at most once. The first
+//! [`take`](EphemeralClassifiedData::take) or
+//! [`expose`](EphemeralClassifiedData::expose) burns it, and so does passing
+//! its deadline. A burned value has already been zeroized, even while the
+//! wrapper itself is still alive.
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use zeroize::Zeroize;
+
+use crate::{async_classified_data::AsyncClassifiedData, classified_data::ClassifiedData};
+
+/// A classified value that can be read once, and optionally only until a deadline.
+///
+/// # Example
+/// ```
+/// use classified::ephemeral_classified_data::EphemeralClassifiedData;
+///
+/// let token = EphemeralClassifiedData::new(b"enroll-1234".to_vec());
+/// assert_eq!(token.expose(|t| t.len()), Some(11));
+/// assert!(token.is_burned());
+/// assert!(token.take().is_none());
+/// ```
+pub struct EphemeralClassifiedData<T: Zeroize> {
+    slot: Arc<Mutex<Option<ClassifiedData<T>>>>,
+    deadline: Option<Instant>,
+}
+
+impl<T: Zeroize> EphemeralClassifiedData<T> {
+    /// Wrap a value that is burned after its first read.
+    pub fn new(data: T) -> Self {
+        Self::from_classified(ClassifiedData::new(data), None)
+    }
+
+    /// Wrap a value that is burned after its first read or once `ttl` has passed.
+    pub fn with_ttl(data: T, ttl: Duration) -> Self {
+        Self::from_classified(ClassifiedData::new(data), Some(Instant::now() + ttl))
+    }
+
+    fn from_classified(data: ClassifiedData<T>, deadline: Option<Instant>) -> Self {
+        Self {
+            slot: Arc::new(Mutex::new(Some(data))),
+            deadline,
+        }
+    }
+
+    /// Move the value out, burning this wrapper.
+    ///
+    /// Returns `None` if the value was already read, burned or expired.
+    pub fn take(&self) -> Option<ClassifiedData<T>> {
+        self.lock_live().take()
+    }
+
+    /// Pass the value to `f` once, then zeroize it.
+    ///
+    /// Returns `None` without calling `f` if the value is no longer available.
+    pub fn expose<R, F>(&self, f: F) -> Option<R>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.take().map(|data| f(data.expose()))
+    }
+
+    /// Zeroize the value now, without reading it.
+    pub fn burn(&self) {
+        lock(&self.slot).take();
+    }
+
+    /// Whether the value has been read, burned or has expired.
+    pub fn is_burned(&self) -> bool {
+        self.lock_live().is_none()
+    }
+
+    /// The instant after which the value is no longer handed out.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Spawn a tokio task that burns the value as soon as the deadline passes.
+    ///
+    /// Without it, an expired value is only wiped on the next access or on
+    /// drop. The task holds a weak reference and does not keep the value
+    /// alive. Returns `None` if there is no deadline.
+    ///
+    /// Must be called from within a tokio runtime.
+    pub fn arm_expiry(&self) -> Option<tokio::task::JoinHandle<()>>
+    where
+        T: Send + 'static,
+    {
+        let deadline = self.deadline?;
+        let slot = Arc::downgrade(&self.slot);
+        Some(tokio::spawn(async move {
+            tokio::time::sleep_until(deadline.into()).await;
+            if let Some(slot) = slot.upgrade() {
+                lock(&slot).take();
+            }
+        }))
+    }
+
+    /// Lock the slot, burning the value first if its deadline has passed.
+    fn lock_live(&self) -> MutexGuard<'_, Option<ClassifiedData<T>>> {
+        let mut slot = lock(&self.slot);
+        if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            slot.take();
+        }
+        slot
+    }
+}
+
+impl<T: Zeroize> EphemeralClassifiedData<T> {
+    /// Move the value into an [`AsyncClassifiedData`], burning this wrapper.
+    pub fn take_async(&self) -> Option<AsyncClassifiedData<T>> {
+        self.take().map(|data| AsyncClassifiedData::new(data.into_value()))
+    }
+}
+
+/// Burn-after-read wrapper around an existing classified value.
+impl<T: Zeroize> From<ClassifiedData<T>> for EphemeralClassifiedData<T> {
+    fn from(data: ClassifiedData<T>) -> Self {
+        Self::from_classified(data, None)
+    }
+}
+
+impl<T: Zeroize> fmt::Debug for EphemeralClassifiedData<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EphemeralClassifiedData(<REDACTED>)")
+    }
+}
+
+/// Poisoning only means another reader panicked; the slot is still valid.
+fn lock<T>(slot: &Mutex<T>) -> MutexGuard<'_, T> {
+    slot.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn value_is_handed_out_once() {
+        let token = EphemeralClassifiedData::new(vec![1u8, 2, 3]);
+        let first = token.take().unwrap();
+        assert_eq!(first.expose(), &[1, 2, 3]);
+        assert!(token.take().is_none());
+        assert!(token.expose(|_| ()).is_none());
+    }
+
+    #[test]
+    fn burn_wipes_without_reading() {
+        let token = EphemeralClassifiedData::new(7u32);
+        token.burn();
+        assert!(token.is_burned());
+    }
+
+    #[test]
+    fn expired_value_is_not_handed_out() {
+        let token = EphemeralClassifiedData::with_ttl(7u32, Duration::ZERO);
+        assert!(token.is_burned());
+        assert!(token.take().is_none());
+    }
+
+    #[test]
+    fn converts_from_classified_data() {
+        let token: EphemeralClassifiedData<_> = ClassifiedData::new(vec![9u8]).into();
+        assert_eq!(token.expose(|t| t.clone()), Some(vec![9u8]));
+    }
+
+    #[tokio::test]
+    async fn armed_timer_burns_after_deadline() {
+        let token = EphemeralClassifiedData::with_ttl(vec![1u8; 8], Duration::from_millis(20));
+        let timer = token.arm_expiry().unwrap();
+        timer.await.unwrap();
+        assert!(lock(&token.slot).is_none());
+    }
+
+    #[tokio::test]
+    async fn take_async_moves_value() {
+        let token = EphemeralClassifiedData::new("bootstrap".to_string());
+        let shared = token.take_async().unwrap();
+        assert_eq!(*shared.expose().await, "bootstrap");
+        assert!(token.take_async().is_none());
+    }
+
+    #[test]
+    fn debug_is_redacted() {
+        let token = EphemeralClassifiedData::new(1u8);
+        assert_eq!(format!("{:?}", token), "EphemeralClassifiedData(<REDACTED>)");
+    }
+}
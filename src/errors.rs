@@ -94,7 +94,11 @@ pub enum SecureError {
     #[error("Pipeline setup failed: {0}")]
     PipelineError(String),
     #[error("Hex code error")]
-    HexCodeError(HexError)
+    HexCodeError(HexError),
+    #[error("Access denied: {0}")]
+    AccessDenied(#[from] AccessError),
+    #[error("Expected {expected} bytes, got {actual}")]
+    InvalidLength { expected: usize, actual: usize },
 }
 #[derive(Error, Debug)]
 pub enum ConcurrencyError {
@@ -123,9 +127,19 @@ pub enum CryptoError {
     AESEncryptionError,
     #[error("RSA Decryption failed")]
     RSAEncryptionError,
+    #[error("RSA support is disabled; enable the `rsa` feature")]
+    RSADisabled,
     #[error("Decryption failed")]
     EncryptionError,
 
+    #[error("Key generation failed: {0}")]
+    KeyGenerationError(String),
+
+    #[error("Signing failed")]
+    SigningError,
+    #[error("Signature verification failed")]
+    InvalidSignature,
+
 
 
     // #[error("Decryption failed")]
@@ -140,6 +154,84 @@ pub enum CryptoError {
 }
 
 
+/// Reasons an [`ExposurePolicy`](crate::exposure_aware_classified_data::ExposurePolicy)
+/// refuses to expose a secret.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ExposureError {
+    #[error("Exposure purpose {0:?} is not allowed by policy")]
+    PurposeNotAllowed(crate::exposure_aware_classified_data::ExposurePurpose),
+    #[error("Policy requires an exposure purpose")]
+    PurposeRequired,
+    #[error("Read quota of {0} exposures exceeded")]
+    ReadQuotaExceeded(usize),
+    #[error("Secret expired")]
+    Expired,
+    #[error("Exposed again before the minimum interval of {0:?}")]
+    TooFrequent(std::time::Duration),
+}
+
+/// Malformed input to the constant-time decoders in
+/// [`constant_time`](crate::constant_time).
+///
+/// Deliberately does not say which character was rejected.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum EncodingError {
+    #[error("Invalid encoded length {0}")]
+    InvalidLength(usize),
+    #[error("Invalid character in encoded input")]
+    InvalidCharacter,
+}
+
+/// Failures splitting or recombining secrets in [`shamir`](crate::shamir).
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ShamirError {
+    #[error("Threshold {threshold} is invalid for {shares} shares")]
+    InvalidThreshold { threshold: u8, shares: u8 },
+    #[error("Need {needed} shares, got {got}")]
+    NotEnoughShares { needed: u8, got: usize },
+    #[error("Share {0} given more than once")]
+    DuplicateShare(u8),
+    #[error("Shares come from different splits")]
+    Mismatched,
+    #[error("Unsupported share version {0}")]
+    UnsupportedVersion(u8),
+    #[error("Checksum mismatch in share {0}")]
+    ChecksumMismatch(u8),
+    #[error("Malformed share")]
+    Malformed,
+    #[error("Share encoding: {0}")]
+    Encoding(#[from] EncodingError),
+    #[error("OS random number generator failed: {0}")]
+    Rng(getrandom::Error),
+}
+
+/// Mandatory access control violations reported by an
+/// [`AccessChecker`](crate::access_control::AccessChecker).
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AccessError {
+    #[error("No read up: clearance {clearance} does not dominate {object}")]
+    ReadUp { clearance: String, object: String },
+    #[error("No write down: {object} does not dominate {subject}")]
+    WriteDown { subject: String, object: String },
+    #[error("Unknown compartment {0}")]
+    UnknownCompartment(String),
+    #[error("No clearance: a layer without clearance cannot read {object}")]
+    NoClearance { object: String },
+}
+
+/// Failures reported by a [`SecretProvider`](crate::secret_provider::SecretProvider).
+#[derive(Error, Debug)]
+pub enum ProviderError {
+    #[error("Secret source not found: {0}")]
+    NotFound(String),
+    #[error("Secret source IO error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Secret command exited with status {0:?}")]
+    CommandFailed(Option<i32>),
+    #[error("Secret provider error: {0}")]
+    Other(String),
+}
+
 #[derive(Error, Debug)]
 pub enum ClassifiedError {
     #[error("Configuration error: {0}")]
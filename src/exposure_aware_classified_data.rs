@@ -26,17 +26,24 @@
 
 
 use std::{
-    sync::atomic::{AtomicUsize, Ordering},
-    time::{SystemTime, UNIX_EPOCH}
+    panic::Location,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex, RwLock,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH}
 };
 
 use secrecy::{ExposeSecret, SecretBox};
+use subtle::{Choice, ConstantTimeEq};
 use zeroize::Zeroize;
 
+use crate::{declassification::Principal, errors::ExposureError};
+
 #[cfg(feature = "logging")]
 use tracing::warn;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ExposurePurpose {
     Signing,
     Decryption,
@@ -44,51 +51,345 @@ pub enum ExposurePurpose {
     Audit,
 }
 
+/// A structured record of a single `expose`/`expose_for` call.
+#[derive(Debug, Clone)]
+pub struct ExposureEvent {
+    /// Process-unique id of the exposed instance.
+    pub instance_id: u64,
+    /// The declared purpose, or `None` for a plain `expose`.
+    pub purpose: Option<ExposurePurpose>,
+    /// Where the secret was exposed from.
+    pub location: &'static Location<'static>,
+    /// When the exposure happened.
+    pub time: SystemTime,
+    /// Number of granted exposures of this instance, including this one.
+    pub count: usize,
+    /// Why the policy refused this exposure, or `None` if it was granted.
+    pub refused: Option<ExposureError>,
+}
+
+/// Receives an [`ExposureEvent`] for every exposure of a secret.
+///
+/// Install one for the whole process with [`set_global_audit_sink`] or for a
+/// single value with [`ExposureAwareClassifiedData::with_audit_sink`].
+pub trait ExposureAuditSink: Send + Sync {
+    fn record(&self, event: &ExposureEvent);
+}
+
+static GLOBAL_AUDIT_SINK: RwLock<Option<Arc<dyn ExposureAuditSink>>> = RwLock::new(None);
+static NEXT_INSTANCE_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Installs the sink that receives events from instances without their own sink.
+pub fn set_global_audit_sink(sink: Arc<dyn ExposureAuditSink>) {
+    *GLOBAL_AUDIT_SINK.write().unwrap_or_else(|e| e.into_inner()) = Some(sink);
+}
+
+/// Removes the global sink installed by [`set_global_audit_sink`].
+pub fn clear_global_audit_sink() {
+    *GLOBAL_AUDIT_SINK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+fn global_audit_sink() -> Option<Arc<dyn ExposureAuditSink>> {
+    GLOBAL_AUDIT_SINK.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Rules that every exposure of a secret must satisfy.
+///
+/// A default policy allows everything. Each builder method adds one
+/// restriction.
+///
+/// # Example
+/// ```
+/// use classified::exposure_aware_classified_data::{
+///     ExposureAwareClassifiedData, ExposurePolicy, ExposurePurpose,
+/// };
+///
+/// let key = ExposureAwareClassifiedData::new([7u8; 32]).with_policy(
+///     ExposurePolicy::new()
+///         .allow_purposes([ExposurePurpose::Signing])
+///         .max_reads(1),
+/// );
+///
+/// assert!(key.expose_for(ExposurePurpose::Decryption).is_err());
+/// assert!(key.expose_for(ExposurePurpose::Signing).is_ok());
+/// assert!(key.expose_for(ExposurePurpose::Signing).is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExposurePolicy {
+    allowed_purposes: Option<Vec<ExposurePurpose>>,
+    max_reads: Option<usize>,
+    not_after: Option<SystemTime>,
+    min_interval: Option<Duration>,
+}
+
+impl ExposurePolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only allow exposures for these purposes. Plain `expose` calls, which
+    /// carry no purpose, are refused once an allow-list is set.
+    pub fn allow_purposes(mut self, purposes: impl IntoIterator<Item = ExposurePurpose>) -> Self {
+        self.allowed_purposes = Some(purposes.into_iter().collect());
+        self
+    }
+
+    /// Allow at most `max` granted exposures over the lifetime of the value.
+    pub fn max_reads(mut self, max: usize) -> Self {
+        self.max_reads = Some(max);
+        self
+    }
+
+    /// Refuse every exposure after `deadline`.
+    pub fn not_after(mut self, deadline: SystemTime) -> Self {
+        self.not_after = Some(deadline);
+        self
+    }
+
+    /// Require at least `interval` between two granted exposures.
+    pub fn min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = Some(interval);
+        self
+    }
+
+    fn check(
+        &self,
+        purpose: Option<ExposurePurpose>,
+        reads: usize,
+        last: Option<SystemTime>,
+        now: SystemTime,
+    ) -> Result<(), ExposureError> {
+        if let Some(allowed) = &self.allowed_purposes {
+            match purpose {
+                None => return Err(ExposureError::PurposeRequired),
+                Some(p) if !allowed.contains(&p) => {
+                    return Err(ExposureError::PurposeNotAllowed(p))
+                }
+                Some(_) => {}
+            }
+        }
+        if let Some(max) = self.max_reads
+            && reads >= max
+        {
+            return Err(ExposureError::ReadQuotaExceeded(max));
+        }
+        if let Some(deadline) = self.not_after
+            && now > deadline
+        {
+            return Err(ExposureError::Expired);
+        }
+        if let (Some(interval), Some(last)) = (self.min_interval, last)
+            && now.duration_since(last).unwrap_or_default() < interval
+        {
+            return Err(ExposureError::TooFrequent(interval));
+        }
+        Ok(())
+    }
+}
+
+/// Exposure counters, shared by every clone of a policy-bound value so the
+/// clones draw on a single quota.
+#[derive(Default)]
+struct Usage {
+    exposures: AtomicUsize,
+    /// Nanoseconds since the Unix epoch of the last exposure, `0` if never.
+    last_exposed: AtomicU64,
+    /// Serializes policy checks so quotas and intervals cannot be raced.
+    gate: Mutex<()>,
+}
+
 pub struct ExposureAwareClassifiedData<T: Zeroize> {
     data: SecretBox<T>,
+    id: u64,
+    usage: Arc<Usage>,
+    sink: Option<Arc<dyn ExposureAuditSink>>,
+    policy: Option<ExposurePolicy>,
 }
 
-static EXPOSURE_COUNT: AtomicUsize = AtomicUsize::new(0);
-
-impl_new!(ExposureAwareClassifiedData);
 impl<T: Zeroize> ExposureAwareClassifiedData<T> {
+    /// Create a new classified value with its own exposure counters.
+    pub fn new(data: T) -> Self {
+        ExposureAwareClassifiedData {
+            data: SecretBox::new(Box::new(data)),
+            id: NEXT_INSTANCE_ID.fetch_add(1, Ordering::Relaxed),
+            usage: Arc::default(),
+            sink: None,
+            policy: None,
+        }
+    }
+
+    /// Enforce `policy` on every exposure of this value.
+    pub fn with_policy(mut self, policy: ExposurePolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// The policy attached with [`ExposureAwareClassifiedData::with_policy`].
+    pub fn policy(&self) -> Option<&ExposurePolicy> {
+        self.policy.as_ref()
+    }
+
+    /// Wrap a value derived from this one under the same sink and policy.
+    ///
+    /// A policy-bound result shares this instance's counters, so reads of
+    /// either draw on one quota; without a policy it starts fresh.
+    fn derive(&self, value: T) -> Self {
+        let mut derived = Self::new(value);
+        derived.sink = self.sink.clone();
+        derived.policy = self.policy.clone();
+        if derived.policy.is_some() {
+            derived.usage = Arc::clone(&self.usage);
+        }
+        derived
+    }
+
+    /// Send this instance's exposure events to `sink` instead of the global one.
+    pub fn with_audit_sink(mut self, sink: Arc<dyn ExposureAuditSink>) -> Self {
+        self.sink = Some(sink);
+        self
+    }
+
+    /// Process-unique id reported in this instance's [`ExposureEvent`]s.
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
     #[must_use = "You must never ignore sensitive data"]
+    #[track_caller]
     /// Safely expose secret with logging + audit hooks
+    ///
+    /// # Panics
+    /// Panics if the attached [`ExposurePolicy`] refuses the exposure. Use
+    /// [`try_expose`](Self::try_expose) or [`expose_for`](Self::expose_for)
+    /// on policy-bound values.
     pub fn expose(&self) -> &T {
-        let count = EXPOSURE_COUNT.fetch_add(1, Ordering::SeqCst);
-        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        match self.try_expose() {
+            Ok(data) => data,
+            Err(e) => panic!("exposure refused by policy: {e}"),
+        }
+    }
 
-        #[cfg(feature = "logging")]
-        // 👁️ Hooked exposure log
-        warn!(target: "sensitive", time = %now, count, "Sensitive value exposed");
+    /// Expose without a declared purpose, honouring the attached policy.
+    #[track_caller]
+    pub fn try_expose(&self) -> Result<&T, ExposureError> {
+        self.record(None)?;
+        Ok(self.data.expose_secret())
+    }
+
+    /// Number of times this instance has been exposed, including exposures of
+    /// clones that share its [`ExposurePolicy`] quota.
+    pub fn exposure_count(&self) -> usize {
+        self.usage.exposures.load(Ordering::SeqCst)
+    }
 
-        self.data.expose_secret()
+    /// When this instance was last exposed, if ever.
+    pub fn last_exposed(&self) -> Option<SystemTime> {
+        match self.usage.last_exposed.load(Ordering::SeqCst) {
+            0 => None,
+            nanos => Some(UNIX_EPOCH + Duration::from_nanos(nanos)),
+        }
     }
 
-    pub fn exposure_count() -> usize {
-        EXPOSURE_COUNT.load(Ordering::SeqCst)
+    /// Expose the secret for a declared purpose.
+    ///
+    /// Fails with an [`ExposureError`] if the attached policy refuses it.
+    #[track_caller]
+    pub fn expose_for(&self, purpose: ExposurePurpose) -> Result<&T, ExposureError> {
+        self.record(Some(purpose))?;
+        Ok(self.data.expose_secret())
     }
 
-    pub fn expose_for(&self, purpose: ExposurePurpose) -> &T {
+    /// Checks the policy, updates the counters and hands an [`ExposureEvent`]
+    /// to the audit sink. Refused exposures are audited but not counted.
+    #[track_caller]
+    fn record(&self, purpose: Option<ExposurePurpose>) -> Result<(), ExposureError> {
+        let _gate = self.usage.gate.lock().unwrap_or_else(|e| e.into_inner());
+        let time = SystemTime::now();
+
+        let refused = self.policy.as_ref().and_then(|policy| {
+            policy
+                .check(purpose, self.exposure_count(), self.last_exposed(), time)
+                .err()
+        });
+
+        let count = if refused.is_none() {
+            let nanos = time
+                .duration_since(UNIX_EPOCH)
+                .map_or(1, |d| d.as_nanos().max(1) as u64);
+            self.usage.last_exposed.store(nanos, Ordering::SeqCst);
+            self.usage.exposures.fetch_add(1, Ordering::SeqCst) + 1
+        } else {
+            self.exposure_count()
+        };
+
+        let event = ExposureEvent {
+            instance_id: self.id,
+            purpose,
+            location: Location::caller(),
+            time,
+            count,
+            refused: refused.clone(),
+        };
+
         #[cfg(feature = "logging")]
-        warn!(target: "security_audit", purpose = ?purpose, "Sensitive material accessed");
-        self.data.expose_secret()
+        // 👁️ Hooked exposure log
+        warn!(
+            target: "security_audit",
+            instance = event.instance_id,
+            purpose = ?event.purpose,
+            location = %event.location,
+            count,
+            refused = ?event.refused,
+            "Sensitive value exposed"
+        );
+
+        if let Some(sink) = self.sink.clone().or_else(global_audit_sink) {
+            sink.record(&event);
+        }
+
+        match refused {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
     }
 }
 impl_for_generics_no_trait!(
     ExposureAwareClassifiedData<T>,
+    #[track_caller]
     pub fn exposed(&self) -> &T {
-        let count = EXPOSURE_COUNT.fetch_add(1, Ordering::SeqCst);
-        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-
-        #[cfg(feature = "logging")]
-        // 👁️ Hooked exposure log
-        warn!(target: "sensitive", time = %now, count, "Sensitive value exposed");
-
-        self.data.expose_secret()
+        self.expose()
     }
 );
 
+impl<T: Zeroize> ExposureAwareClassifiedData<T> {
+    /// Release the value as plain data on the authority of `authorizer`.
+    ///
+    /// The release is recorded through the
+    /// [declassification sink](crate::declassification), not as an exposure,
+    /// and is not subject to the attached [`ExposurePolicy`].
+    ///
+    /// # Panics
+    /// Panics if `reason` is blank.
+    #[track_caller]
+    pub fn declassify(self, reason: &str, authorizer: &Principal) -> T {
+        crate::declassification::audit("ExposureAwareClassifiedData", None, reason, authorizer);
+
+        let this = std::mem::ManuallyDrop::new(self);
+        // SAFETY: `this` is never used or dropped again, so reading the
+        // fields out transfers sole ownership of them.
+        let (data, usage, sink, policy) = unsafe {
+            (
+                std::ptr::read(&this.data),
+                std::ptr::read(&this.usage),
+                std::ptr::read(&this.sink),
+                std::ptr::read(&this.policy),
+            )
+        };
+        drop((usage, sink, policy));
+        crate::zeroizing_guard::unbox_secret(data)
+    }
+}
+
 impl ExposureAwareClassifiedData<Vec<u8>> {
     pub async fn update<F>(&self, f: F)
     where
@@ -99,12 +400,58 @@ impl ExposureAwareClassifiedData<Vec<u8>> {
     }
 }
 
-use crate::{impl_clone, impl_ct, impl_debug, impl_for_generics_no_trait, impl_generic_drop, impl_new};
-impl_clone!(ExposureAwareClassifiedData);
+#[cfg(not(feature = "no-clone"))]
+/// Clones the secret into a new instance with its own id.
+///
+/// The audit sink and policy are carried over. A policy-bound clone shares
+/// the original's counters, so reads through either one count against the
+/// same quota and interval; without a policy the clone starts fresh.
+///
+/// ⚠️ Cloning secrets can be dangerous and should only be enabled when necessary.
+impl<T: Zeroize + Clone> Clone for ExposureAwareClassifiedData<T> {
+    fn clone(&self) -> Self {
+        self.derive(self.data.expose_secret().clone())
+    }
+}
+
+#[cfg(feature = "no-clone")]
+/// Prevents cloning of classified data when `no-clone` is enabled,
+/// enforcing strong immutability of secrets.
+impl<T: Zeroize + Clone> Clone for ExposureAwareClassifiedData<T> {
+    fn clone(&self) -> Self {
+        panic!("Cloning sensitive data is forbidden");
+    }
+}
+
+use crate::{impl_debug, impl_for_generics_no_trait, impl_generic_drop};
 impl_generic_drop!(ExposureAwareClassifiedData<T>, data);
 // impl_drop!(ExposureAwareClassifiedData);
 impl_debug!(ExposureAwareClassifiedData);
-impl_ct!(ExposureAwareClassifiedData);
+
+impl ExposureAwareClassifiedData<Vec<u8>> {
+    /// Compare in constant time, counting one exposure of each value.
+    ///
+    /// Fails with the first [`ExposureError`] either policy raises; the
+    /// refusal is audited like any other.
+    #[track_caller]
+    pub fn try_ct_eq(&self, other: &Self) -> Result<Choice, ExposureError> {
+        let lhs = self.try_expose()?;
+        let rhs = other.try_expose()?;
+        Ok(lhs.ct_eq(rhs))
+    }
+}
+
+/// Counts as an exposure of both values, so comparisons draw on the policy's
+/// quota. A refused comparison is audited and reports "not equal".
+impl ConstantTimeEq for ExposureAwareClassifiedData<Vec<u8>> {
+    #[track_caller]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.try_ct_eq(other).unwrap_or_else(|_| Choice::from(0))
+    }
+}
+
+#[cfg(feature = "async")]
+crate::impl_ct_ops!(audited ExposureAwareClassifiedData);
 
 
 
@@ -115,6 +462,7 @@ impl_ct!(ExposureAwareClassifiedData);
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::errors::ExposureError;
     use subtle::{ConstantTimeEq};
     
     #[cfg(feature = "concurrency")]
@@ -134,17 +482,175 @@ mod tests {
     fn exposes_value_and_increments_count() {
         init_tracing();
         let sensitive = ExposureAwareClassifiedData::new(1234u32);
-        let before = ExposureAwareClassifiedData::<u32>::exposure_count();
+        let before = sensitive.exposure_count();
         assert_eq!(*sensitive.expose(), 1234);
-        let after = ExposureAwareClassifiedData::<u32>::exposure_count();
+        let after = sensitive.exposure_count();
         assert_eq!(after, before + 1);
     }
 
     #[test]
-    fn clone_preserves_value_but_not_count() {
+    fn counters_are_per_instance() {
+        let a = ExposureAwareClassifiedData::new(1u8);
+        let b = ExposureAwareClassifiedData::new(2u8);
+        assert!(a.last_exposed().is_none());
+
+        let _ = a.expose();
+        let _ = a.expose_for(ExposurePurpose::Audit);
+
+        assert_eq!(a.exposure_count(), 2);
+        assert_eq!(b.exposure_count(), 0);
+        assert!(a.last_exposed().is_some());
+        assert!(b.last_exposed().is_none());
+        assert_ne!(a.id(), b.id());
+    }
+
+    #[cfg(not(feature = "no-clone"))]
+    #[test]
+    fn clone_without_policy_gets_fresh_counters() {
         let sensitive = ExposureAwareClassifiedData::new("secure".to_string());
         let cloned = sensitive.clone();
         assert_eq!(sensitive.expose(), cloned.expose());
+        assert_eq!(sensitive.exposure_count(), 1);
+        assert_eq!(cloned.exposure_count(), 1);
+        assert_ne!(sensitive.id(), cloned.id());
+    }
+
+    #[cfg(not(feature = "no-clone"))]
+    #[test]
+    fn clones_share_the_read_quota() {
+        let key = ExposureAwareClassifiedData::new(vec![1u8])
+            .with_policy(ExposurePolicy::new().max_reads(1));
+        let cloned = key.clone();
+
+        assert!(cloned.expose_for(ExposurePurpose::Signing).is_ok());
+        assert!(matches!(
+            key.expose_for(ExposurePurpose::Signing),
+            Err(ExposureError::ReadQuotaExceeded(1))
+        ));
+        assert!(key.clone().expose_for(ExposurePurpose::Signing).is_err());
+        assert_eq!(key.exposure_count(), 1);
+    }
+
+    #[derive(Default)]
+    struct RecordingSink(std::sync::Mutex<Vec<ExposureEvent>>);
+
+    impl ExposureAuditSink for RecordingSink {
+        fn record(&self, event: &ExposureEvent) {
+            self.0.lock().unwrap().push(event.clone());
+        }
+    }
+
+    #[test]
+    fn instance_sink_receives_structured_events() {
+        let sink = Arc::new(RecordingSink::default());
+        let sensitive = ExposureAwareClassifiedData::new(vec![1u8, 2])
+            .with_audit_sink(sink.clone());
+
+        let line = line!() + 1;
+        let _ = sensitive.expose_for(ExposurePurpose::Signing);
+        let _ = sensitive.expose();
+
+        let events = sink.0.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].instance_id, sensitive.id());
+        assert_eq!(events[0].purpose, Some(ExposurePurpose::Signing));
+        assert_eq!(events[0].location.file(), file!());
+        assert_eq!(events[0].location.line(), line);
+        assert_eq!(events[0].count, 1);
+        assert_eq!(events[1].purpose, None);
+        assert_eq!(events[1].count, 2);
+    }
+
+    #[test]
+    fn policy_restricts_purposes() {
+        let key = ExposureAwareClassifiedData::new(1u8)
+            .with_policy(ExposurePolicy::new().allow_purposes([ExposurePurpose::Signing]));
+
+        assert!(key.expose_for(ExposurePurpose::Signing).is_ok());
+        assert_eq!(
+            key.expose_for(ExposurePurpose::Decryption).unwrap_err(),
+            ExposureError::PurposeNotAllowed(ExposurePurpose::Decryption)
+        );
+        assert_eq!(key.try_expose().unwrap_err(), ExposureError::PurposeRequired);
+        assert_eq!(key.exposure_count(), 1);
+    }
+
+    #[test]
+    fn policy_enforces_read_quota() {
+        let key = ExposureAwareClassifiedData::new(1u8)
+            .with_policy(ExposurePolicy::new().max_reads(2));
+
+        assert!(key.try_expose().is_ok());
+        assert!(key.expose_for(ExposurePurpose::Audit).is_ok());
+        assert_eq!(key.try_expose().unwrap_err(), ExposureError::ReadQuotaExceeded(2));
+    }
+
+    #[test]
+    fn policy_enforces_expiry_and_interval() {
+        let expired = ExposureAwareClassifiedData::new(1u8).with_policy(
+            ExposurePolicy::new().not_after(SystemTime::now() - Duration::from_secs(1)),
+        );
+        assert_eq!(expired.try_expose().unwrap_err(), ExposureError::Expired);
+
+        let throttled = ExposureAwareClassifiedData::new(1u8)
+            .with_policy(ExposurePolicy::new().min_interval(Duration::from_secs(3600)));
+        assert!(throttled.try_expose().is_ok());
+        assert!(matches!(throttled.try_expose(), Err(ExposureError::TooFrequent(_))));
+    }
+
+    #[test]
+    #[should_panic(expected = "exposure refused by policy")]
+    fn plain_expose_panics_when_policy_refuses() {
+        let key = ExposureAwareClassifiedData::new(1u8)
+            .with_policy(ExposurePolicy::new().max_reads(0));
+        let _ = key.expose();
+    }
+
+    #[test]
+    fn refused_exposures_are_audited() {
+        let sink = Arc::new(RecordingSink::default());
+        let key = ExposureAwareClassifiedData::new(1u8)
+            .with_policy(ExposurePolicy::new().max_reads(0))
+            .with_audit_sink(sink.clone());
+
+        assert!(key.try_expose().is_err());
+
+        let events = sink.0.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].refused, Some(ExposureError::ReadQuotaExceeded(0)));
+        assert_eq!(events[0].count, 0);
+    }
+
+    #[test]
+    fn declassify_releases_value_and_is_audited() {
+        let sink = crate::declassification::tests::recording_sink();
+        let key = ExposureAwareClassifiedData::new(vec![4u8, 2])
+            .with_policy(ExposurePolicy::new().max_reads(0));
+
+        let public = key.declassify("exposure-aware release test", &Principal::new("carol"));
+        assert_eq!(public, vec![4, 2]);
+
+        let records = sink.0.lock().unwrap();
+        let record = records
+            .iter()
+            .find(|r| r.reason == "exposure-aware release test")
+            .unwrap();
+        assert_eq!(record.source, "ExposureAwareClassifiedData");
+    }
+
+    #[test]
+    fn global_sink_receives_events_without_instance_sink() {
+        let sink = Arc::new(RecordingSink::default());
+        set_global_audit_sink(sink.clone());
+
+        let sensitive = ExposureAwareClassifiedData::new(7u64);
+        let _ = sensitive.expose_for(ExposurePurpose::KeyWrapping);
+        clear_global_audit_sink();
+
+        let events = sink.0.lock().unwrap();
+        assert!(events.iter().any(|e| {
+            e.instance_id == sensitive.id() && e.purpose == Some(ExposurePurpose::KeyWrapping)
+        }));
     }
 
     #[test]
@@ -164,6 +670,26 @@ mod tests {
         assert!(!bool::from(a.ct_eq(&c)));
     }
 
+    #[test]
+    fn ct_eq_uses_up_max_reads() {
+        let sink = Arc::new(RecordingSink::default());
+        let key = ExposureAwareClassifiedData::new(vec![7u8; 4])
+            .with_policy(ExposurePolicy::new().max_reads(2))
+            .with_audit_sink(sink.clone());
+        let guess = ExposureAwareClassifiedData::new(vec![7u8; 4]);
+
+        assert!(bool::from(key.ct_eq(&guess)));
+        assert!(bool::from(key.ct_eq(&guess)));
+        assert_eq!(key.exposure_count(), 2);
+
+        assert!(!bool::from(key.ct_eq(&guess)));
+        assert!(matches!(key.try_ct_eq(&guess), Err(ExposureError::ReadQuotaExceeded(2))));
+
+        let events = sink.0.lock().unwrap();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events.iter().filter(|e| e.refused.is_some()).count(), 2);
+    }
+
     #[tokio::test]
     async fn async_update_clones_and_modifies() {
         let data = ExposureAwareClassifiedData::new(vec![10, 20, 30]);
@@ -175,4 +701,38 @@ mod tests {
         // So original stays untouched
         assert_eq!(data.expose(), &[10, 20, 30]);
     }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn constant_time_ops_count_against_the_quota() {
+        use crate::constant_time::ConstantTimeOps;
+
+        let policy = ExposurePolicy::new().max_reads(3);
+        let a = ExposureAwareClassifiedData::new(vec![1u8, 2, 3]).with_policy(policy.clone());
+        let b = ExposureAwareClassifiedData::new(vec![1u8, 2, 4]).with_policy(policy);
+
+        assert_eq!(a.to_hex().expose(), "010203");
+        assert_eq!(a.exposure_count(), 1);
+
+        assert!(bool::from(a.ct_lt(&b)));
+        assert_eq!((a.exposure_count(), b.exposure_count()), (2, 1));
+
+        let picked = ExposureAwareClassifiedData::ct_conditional_select(&a, &b, Choice::from(1));
+        assert_eq!((a.exposure_count(), b.exposure_count()), (3, 2));
+
+        // The selected copy draws on `a`'s exhausted quota.
+        assert!(matches!(picked.try_expose(), Err(ExposureError::ReadQuotaExceeded(3))));
+        assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| a.to_base64())).is_err());
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    #[should_panic(expected = "conditional select needs equal policies")]
+    fn constant_time_select_needs_equal_policies() {
+        use crate::constant_time::ConstantTimeOps;
+
+        let a = ExposureAwareClassifiedData::new(vec![1u8]).with_policy(ExposurePolicy::new().max_reads(1));
+        let b = ExposureAwareClassifiedData::new(vec![2u8]);
+        let _ = ExposureAwareClassifiedData::ct_conditional_select(&a, &b, Choice::from(1));
+    }
 }
@@ -0,0 +1,129 @@
+//! ----------------------------------------------
+//! DOCUMENT DETAILS -----------------------------
+//!
+//! filename:gf.rs
+//! description:constant-time Galois field arithmetic
+//! usages:none in crate yet
+//!
+//! ----------------------------------------------
+//! FEATURE NOTES --------------------------------
+//!
+//! feature_name:async
+//! deps:[tokio][async_trait]
+//! scope:[impl Add/Sub/Mul for &ClassifiedData]
+//! corpus:true
+//!
+//! feature_name:none
+//! deps:[subtle][zeroize]
+//! scope:[trait Field][struct Gf256][struct Gf128][struct Fp]
+//! corpus:true
+//!
+//! ----------------------------------------------
+//! CORPUS FEATURES ------------------------------
+//!
+
+//! Constant-time finite field arithmetic.
+//!
+//! - [`Gf256`]: GF(2^8) with the AES polynomial, as used for secret sharing.
+//! - [`Gf128`]: GF(2^128) in the bit order of GHASH (NIST SP 800-38D).
+//! - [`Fp`]: the prime field of integers modulo a const-generic prime `P`.
+//!
+//! Multiplication is computed with shifts and masks instead of log/exp
+//! tables, and inversion by exponentiation, so no memory access or branch
+//! depends on an element's value. Elements zeroize and redact their `Debug`
+//! output, and arithmetic is available directly on
+//! [`ClassifiedData`](crate::classified_data::ClassifiedData) holding them.
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+use subtle::{ConditionallySelectable, ConstantTimeEq, CtOption};
+use zeroize::Zeroize;
+
+pub mod gf128;
+pub mod gf256;
+pub mod prime;
+
+pub use gf128::Gf128;
+pub use gf256::Gf256;
+pub use prime::Fp;
+
+/// A finite field whose operations run in constant time.
+pub trait Field:
+    Copy
+    + Default
+    + ConstantTimeEq
+    + ConditionallySelectable
+    + Zeroize
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn square(self) -> Self {
+        self * self
+    }
+
+    /// The multiplicative inverse; none for zero.
+    fn invert(self) -> CtOption<Self>;
+}
+
+#[cfg(feature = "async")]
+mod classified {
+    use std::ops::{Add, Mul, Sub};
+
+    use subtle::CtOption;
+
+    use super::Field;
+    use crate::{classification::Level, classified_data::ClassifiedData};
+
+    macro_rules! classified_op {
+        ($trait_name:ident, $method:ident) => {
+            /// Combines two classified field elements without exposing
+            /// either; the result carries both sets of compartments.
+            impl<F: Field, L: Level> $trait_name for &ClassifiedData<F, L> {
+                type Output = ClassifiedData<F, L>;
+
+                fn $method(self, rhs: Self) -> Self::Output {
+                    self.zip(rhs, |a, b, _| $trait_name::$method(*a, *b))
+                }
+            }
+        };
+    }
+
+    classified_op!(Add, add);
+    classified_op!(Sub, sub);
+    classified_op!(Mul, mul);
+
+    impl<F: Field, L: Level> ClassifiedData<F, L> {
+        /// The classified multiplicative inverse; none for zero.
+        pub fn invert(&self) -> CtOption<ClassifiedData<F, L>> {
+            let inverse = self.expose().invert();
+            let is_some = inverse.is_some();
+            CtOption::new(self.map(|_, _| inverse.unwrap_or(F::ZERO)), is_some)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use super::*;
+    use crate::classified_data::ClassifiedData;
+
+    #[test]
+    fn arithmetic_on_classified_elements() {
+        let a = ClassifiedData::new(Gf256::new(0x57)).with_compartments(["KEYS"]);
+        let b = ClassifiedData::new(Gf256::new(0x83));
+
+        let product = &a * &b;
+        assert_eq!(product.expose().value(), 0xc1);
+        assert_eq!(product.compartments().len(), 1);
+        assert_eq!((&a + &b).expose().value(), 0x57 ^ 0x83);
+
+        let inverse = a.invert().unwrap();
+        assert_eq!((&a * &inverse).expose().value(), 1);
+        assert!(bool::from(ClassifiedData::new(Gf256::ZERO).invert().is_none()));
+    }
+}
@@ -0,0 +1,164 @@
+//! GF(2^128) as used by GHASH in AES-GCM (NIST SP 800-38D §6.3).
+//!
+//! GHASH reverses the usual bit order: the coefficient of x^0 is the most
+//! significant bit of the first byte, and the field polynomial
+//! x^128 + x^7 + x^2 + x + 1 appears as the constant `0xe1 << 120`.
+
+use std::{
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+use zeroize::DefaultIsZeroes;
+
+use super::Field;
+
+/// The reduction constant R from SP 800-38D.
+const R: u128 = 0xe1 << 120;
+
+/// An element of the GHASH field.
+///
+/// # Example
+/// ```
+/// use classified::gf::{Field, Gf128};
+///
+/// let h = Gf128::from_bytes([0x42; 16]);
+/// assert_eq!(h * Gf128::ONE, h);
+/// assert_eq!(h * h.invert().unwrap(), Gf128::ONE);
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct Gf128(u128);
+
+impl Gf128 {
+    /// Interpret a 16-byte block in GHASH bit order.
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self(u128::from_be_bytes(bytes))
+    }
+
+    pub const fn to_bytes(self) -> [u8; 16] {
+        self.0.to_be_bytes()
+    }
+}
+
+impl Field for Gf128 {
+    const ZERO: Self = Self(0);
+    const ONE: Self = Self(1 << 127);
+
+    fn invert(self) -> CtOption<Self> {
+        // a^(2^128 - 2) = a^-1, built as a^2 · a^4 · … · a^(2^127).
+        let mut power = self;
+        let mut inverse = Self::ONE;
+        for _ in 0..127 {
+            power = power.square();
+            inverse = inverse * power;
+        }
+        CtOption::new(inverse, !self.ct_eq(&Self::ZERO))
+    }
+}
+
+impl Add for Gf128 {
+    type Output = Self;
+
+    // Addition in characteristic 2 is XOR.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl Sub for Gf128 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self {
+        self + rhs
+    }
+}
+
+impl Neg for Gf128 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self
+    }
+}
+
+impl Mul for Gf128 {
+    type Output = Self;
+
+    /// Algorithm 1 of SP 800-38D with masks in place of branches.
+    fn mul(self, rhs: Self) -> Self {
+        let (x, mut v, mut z) = (self.0, rhs.0, 0u128);
+        for i in (0..128).rev() {
+            z ^= v & 0u128.wrapping_sub((x >> i) & 1);
+            let lsb = 0u128.wrapping_sub(v & 1);
+            v = (v >> 1) ^ (R & lsb);
+        }
+        Self(z)
+    }
+}
+
+impl ConstantTimeEq for Gf128 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl ConditionallySelectable for Gf128 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(u128::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl PartialEq for Gf128 {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for Gf128 {}
+
+impl DefaultIsZeroes for Gf128 {}
+
+impl fmt::Debug for Gf128 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Gf128(<REDACTED>)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(hex: &str) -> Gf128 {
+        Gf128(u128::from_str_radix(hex, 16).unwrap())
+    }
+
+    #[test]
+    fn ghash_matches_gcm_test_case_2() {
+        // AES-GCM test case 2 (K = 0, IV = 0, P = 0^128): GHASH over the
+        // ciphertext block and the length block.
+        let h = block("66e94bd4ef8a2c3b884cfa59ca342b2e");
+        let c = block("0388dace60b6a392f328c2b971b2fe78");
+        let lengths = block("00000000000000000000000000000080");
+
+        let x1 = c * h;
+        let x2 = (x1 + lengths) * h;
+        assert_eq!(x2, block("f38cbb1ad69223dcc3457ae5b6b0f885"));
+    }
+
+    #[test]
+    fn one_is_the_identity_and_inverses_cancel() {
+        let a = block("0123456789abcdeffedcba9876543210");
+        assert_eq!(a * Gf128::ONE, a);
+        assert_eq!(a * a.invert().unwrap(), Gf128::ONE);
+        assert!(bool::from(Gf128::ZERO.invert().is_none()));
+    }
+
+    #[test]
+    fn round_trips_bytes() {
+        let bytes = *b"ghash block 16b!";
+        assert_eq!(Gf128::from_bytes(bytes).to_bytes(), bytes);
+    }
+}
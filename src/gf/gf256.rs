@@ -0,0 +1,171 @@
+//! GF(2^8) modulo the AES polynomial x^8 + x^4 + x^3 + x + 1.
+
+use std::{
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+use zeroize::DefaultIsZeroes;
+
+use super::Field;
+
+/// Low byte of the reduction polynomial; the x^8 term is implicit.
+const POLY: u8 = 0x1b;
+
+/// An element of GF(2^8).
+///
+/// # Example
+/// ```
+/// use classified::gf::{Field, Gf256};
+///
+/// // FIPS-197 §4.2: {57} • {83} = {c1}
+/// let product = Gf256::new(0x57) * Gf256::new(0x83);
+/// assert_eq!(product.value(), 0xc1);
+/// assert_eq!((product * product.invert().unwrap()).value(), 1);
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct Gf256(u8);
+
+impl Gf256 {
+    pub const fn new(value: u8) -> Self {
+        Self(value)
+    }
+
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+}
+
+impl Field for Gf256 {
+    const ZERO: Self = Self(0);
+    const ONE: Self = Self(1);
+
+    fn invert(self) -> CtOption<Self> {
+        // a^254 = a^-1, built as a^2 · a^4 · … · a^128.
+        let mut power = self;
+        let mut inverse = Self::ONE;
+        for _ in 0..7 {
+            power = power.square();
+            inverse = inverse * power;
+        }
+        CtOption::new(inverse, !self.ct_eq(&Self::ZERO))
+    }
+}
+
+impl Add for Gf256 {
+    type Output = Self;
+
+    // Addition in characteristic 2 is XOR.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl Sub for Gf256 {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self {
+        self + rhs
+    }
+}
+
+impl Neg for Gf256 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self
+    }
+}
+
+impl Mul for Gf256 {
+    type Output = Self;
+
+    /// Shift-and-add multiplication with masks in place of branches.
+    fn mul(self, rhs: Self) -> Self {
+        let (mut a, mut b, mut product) = (self.0, rhs.0, 0u8);
+        for _ in 0..8 {
+            product ^= a & 0u8.wrapping_sub(b & 1);
+            let carry = 0u8.wrapping_sub(a >> 7);
+            a = (a << 1) ^ (POLY & carry);
+            b >>= 1;
+        }
+        Self(product)
+    }
+}
+
+impl ConstantTimeEq for Gf256 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl ConditionallySelectable for Gf256 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(u8::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl PartialEq for Gf256 {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for Gf256 {}
+
+impl DefaultIsZeroes for Gf256 {}
+
+impl fmt::Debug for Gf256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Gf256(<REDACTED>)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reference multiplication that branches freely.
+    fn slow_mul(mut a: u8, mut b: u8) -> u8 {
+        let mut p = 0;
+        while b != 0 {
+            if b & 1 == 1 {
+                p ^= a;
+            }
+            a = if a & 0x80 != 0 { (a << 1) ^ POLY } else { a << 1 };
+            b >>= 1;
+        }
+        p
+    }
+
+    #[test]
+    fn multiplication_matches_reference() {
+        for a in 0..=255u8 {
+            for b in [0u8, 1, 2, 0x53, 0x80, 0xca, 0xff] {
+                assert_eq!((Gf256(a) * Gf256(b)).0, slow_mul(a, b));
+            }
+        }
+    }
+
+    #[test]
+    fn every_nonzero_element_inverts() {
+        for a in 1..=255u8 {
+            let inverse = Gf256(a).invert().unwrap();
+            assert_eq!(Gf256(a) * inverse, Gf256::ONE);
+        }
+        // FIPS-197 §4.4: the inverse of {53} is {ca}.
+        assert_eq!(Gf256(0x53).invert().unwrap().value(), 0xca);
+        assert!(bool::from(Gf256::ZERO.invert().is_none()));
+    }
+
+    #[test]
+    fn addition_is_xor_and_self_inverse() {
+        let a = Gf256(0x3c);
+        assert_eq!(a + a, Gf256::ZERO);
+        assert_eq!(a - Gf256(0x0f), Gf256(0x33));
+        assert_eq!(-a, a);
+    }
+}
@@ -0,0 +1,187 @@
+//! The prime field of integers modulo `P`.
+
+use std::{
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+use zeroize::DefaultIsZeroes;
+
+use super::Field;
+
+/// The field modulo the Mersenne prime 2^61 - 1.
+pub type Fp61 = Fp<{ (1 << 61) - 1 }>;
+
+/// An integer modulo the prime `P`.
+///
+/// `P` must be an odd prime; oddness is checked at compile time, primality
+/// is the caller's responsibility (inversion relies on Fermat's little
+/// theorem). Reduction uses conditional subtraction rather than the CPU's
+/// divider, whose timing depends on its operands.
+///
+/// # Example
+/// ```
+/// use classified::gf::{prime::Fp, Field};
+///
+/// type F = Fp<65_521>;
+/// let a = F::new(65_520); // -1
+/// assert_eq!((a * a).value(), 1);
+/// assert_eq!((F::new(3) * F::new(3).invert().unwrap()).value(), 1);
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct Fp<const P: u64>(u64);
+
+impl<const P: u64> Fp<P> {
+    /// Reduce `value` modulo `P`.
+    pub fn new(value: u64) -> Self {
+        const { assert!(P > 2 && P % 2 == 1, "Fp modulus must be an odd prime") };
+        let mut reduced = 0u64;
+        for i in (0..64).rev() {
+            reduced = Self::double_add(reduced, (value >> i) & 1);
+        }
+        Self(reduced)
+    }
+
+    /// The canonical representative in `0..P`.
+    pub const fn value(self) -> u64 {
+        self.0
+    }
+
+    /// `x - P` if `x >= P`, else `x`, for `x < 2P`.
+    fn reduce_once(x: u128) -> u64 {
+        let (diff, borrow) = x.overflowing_sub(P as u128);
+        let keep = Choice::from(borrow as u8);
+        u128::conditional_select(&diff, &x, keep) as u64
+    }
+
+    /// `2r + bit` reduced, for `r < P` and `bit` in `{0, 1}`.
+    fn double_add(r: u64, bit: u64) -> u64 {
+        let doubled = Self::reduce_once((r as u128) << 1);
+        Self::reduce_once(doubled as u128 + bit as u128)
+    }
+}
+
+impl<const P: u64> Field for Fp<P> {
+    const ZERO: Self = Self(0);
+    const ONE: Self = Self(1);
+
+    fn invert(self) -> CtOption<Self> {
+        // a^(P - 2) = a^-1; every exponent bit costs a square and a multiply.
+        let exponent = P - 2;
+        let mut inverse = Self::ONE;
+        for i in (0..64).rev() {
+            inverse = inverse.square();
+            let multiplied = inverse * self;
+            inverse.conditional_assign(&multiplied, Choice::from(((exponent >> i) & 1) as u8));
+        }
+        CtOption::new(inverse, !self.ct_eq(&Self::ZERO))
+    }
+}
+
+impl<const P: u64> Add for Fp<P> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(Self::reduce_once(self.0 as u128 + rhs.0 as u128))
+    }
+}
+
+impl<const P: u64> Sub for Fp<P> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<const P: u64> Neg for Fp<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(Self::reduce_once(P as u128 - self.0 as u128))
+    }
+}
+
+impl<const P: u64> Mul for Fp<P> {
+    type Output = Self;
+
+    /// Double-and-add over the bits of `rhs`, adding `self` under a mask.
+    fn mul(self, rhs: Self) -> Self {
+        let mut product = 0u64;
+        for i in (0..64).rev() {
+            product = Self::double_add(product, 0);
+            let mask = 0u64.wrapping_sub((rhs.0 >> i) & 1);
+            product = Self::reduce_once(product as u128 + (self.0 & mask) as u128);
+        }
+        Self(product)
+    }
+}
+
+impl<const P: u64> ConstantTimeEq for Fp<P> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl<const P: u64> ConditionallySelectable for Fp<P> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(u64::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl<const P: u64> PartialEq for Fp<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<const P: u64> Eq for Fp<P> {}
+
+impl<const P: u64> DefaultIsZeroes for Fp<P> {}
+
+impl<const P: u64> fmt::Debug for Fp<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Fp<{P}>(<REDACTED>)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LARGE: u64 = 18_446_744_073_709_551_557; // largest prime below 2^64
+    type Small = Fp<101>;
+    type Large = Fp<LARGE>;
+
+    #[test]
+    fn reduces_on_construction() {
+        assert_eq!(Small::new(101).value(), 0);
+        assert_eq!(Small::new(u64::MAX).value(), u64::MAX % 101);
+        assert_eq!(Large::new(u64::MAX).value(), u64::MAX - LARGE);
+    }
+
+    #[test]
+    fn arithmetic_matches_wide_integers() {
+        let samples = [0, 1, 2, 12_345_678_901, LARGE - 1, LARGE / 3];
+        for &a in &samples {
+            for &b in &samples {
+                let (x, y) = (Large::new(a), Large::new(b));
+                let p = LARGE as u128;
+                assert_eq!((x + y).value() as u128, (a as u128 + b as u128) % p);
+                assert_eq!((x - y).value() as u128, (a as u128 + p - b as u128) % p);
+                assert_eq!((x * y).value() as u128, (a as u128 * b as u128) % p);
+            }
+        }
+    }
+
+    #[test]
+    fn every_nonzero_element_inverts() {
+        for a in 1..101 {
+            assert_eq!(Small::new(a) * Small::new(a).invert().unwrap(), Small::ONE);
+        }
+        let a = Fp61::new(0xdead_beef_cafe);
+        assert_eq!(a * a.invert().unwrap(), Fp61::ONE);
+        assert!(bool::from(Small::ZERO.invert().is_none()));
+    }
+}
@@ -1,47 +1,125 @@
 //! ----------------------------------------------
 //! DOCUMENT DETAILS -----------------------------
-//! 
+//!
 //! filename:key_manager.rs
 //! description:
 //! usages:none in crate yet
-//! 
+//!
 //! ----------------------------------------------
 //! FEATURE NOTES --------------------------------
-//! 
+//!
 //! feature_name:async
 //! deps:[tokio][async_trait]
 //! scope:[impl ThreadPoolManager]
 //! corpus:true
-//! 
+//!
 //! feature_name:std
 //! deps:[std]
 //! scope:[impl ThreadPoolManager]
 //! corpus:false
-//! 
+//!
 //! ----------------------------------------------
 //! CORPUS FEATURES ------------------------------
-//! 
+//!
 #![cfg(feature = "async")]
 #![cfg(feature = "std")]
 
-
 use crate::classified_data::ClassifiedData;
-use crate::errors::SecureError;
+use crate::crypto::crypto_algorithm::CryptoAlgorithm;
+use crate::crypto::crypto_primitive::CryptoPrimitive;
+use crate::crypto::key_generator::KeyGenerator;
+use crate::crypto::key_length::KeyLength;
+use crate::errors::{CryptoError, SecureError};
+use crate::shielded_classified_data::ShieldedClassifiedData;
+use crate::zeroizing_guard::unbox_secret;
 use std::collections::HashMap;
-use std::time::Duration;
 use std::sync::Arc;
+use std::time::Duration;
 
+use secrecy::SecretBox;
 use subtle::ConstantTimeEq;
 use tokio::sync::Mutex;
 
 #[cfg(feature = "logging")]
-use tracing::info;
+use tracing::{error, info};
 
 pub type SecureMutex<T> = Arc<Mutex<T>>;
 
+/// How a [`KeyManager`] keeps key material in memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StorageMode {
+    /// Keys are held in [`ClassifiedData`].
+    #[default]
+    Plain,
+    /// Keys are held in [`ShieldedClassifiedData`] and only decrypted on use.
+    Shielded,
+}
+
+enum StoredKey {
+    Plain(ClassifiedData<Vec<u8>>),
+    Shielded(ShieldedClassifiedData<Vec<u8>>),
+}
+
+/// A stored key together with the shape it was accepted with, so rotation
+/// can generate a replacement of the same kind.
+struct ManagedKey {
+    algorithm: CryptoAlgorithm,
+    key_length: KeyLength,
+    key: StoredKey,
+}
+
+impl ManagedKey {
+    fn new(mode: StorageMode, primitive: CryptoPrimitive) -> Self {
+        Self {
+            algorithm: primitive.algorithm.clone(),
+            key_length: primitive.key_length.clone(),
+            key: StoredKey::from_classified(mode, take_key_material(primitive)),
+        }
+    }
+}
+
+/// Moves the key bytes out of `primitive` without copying them.
+fn take_key_material(mut primitive: CryptoPrimitive) -> ClassifiedData<Vec<u8>> {
+    let material = std::mem::replace(&mut primitive.key_material, SecretBox::new(Box::default()));
+    ClassifiedData::new(unbox_secret(material))
+}
+
+impl StoredKey {
+    fn from_classified(mode: StorageMode, key: ClassifiedData<Vec<u8>>) -> Self {
+        match mode {
+            StorageMode::Plain => StoredKey::Plain(key),
+            StorageMode::Shielded => StoredKey::Shielded(ShieldedClassifiedData::from(key)),
+        }
+    }
+
+    fn mode(&self) -> StorageMode {
+        match self {
+            StoredKey::Plain(_) => StorageMode::Plain,
+            StoredKey::Shielded(_) => StorageMode::Shielded,
+        }
+    }
+
+    fn with_exposed<R>(&self, f: impl FnOnce(&Vec<u8>) -> R) -> Option<R> {
+        match self {
+            StoredKey::Plain(key) => Some(f(key.expose())),
+            StoredKey::Shielded(key) => key.expose(f).ok(),
+        }
+    }
+
+    fn to_classified(&self) -> Option<ClassifiedData<Vec<u8>>> {
+        match self {
+            StoredKey::Plain(key) => Some(key.clone()),
+            StoredKey::Shielded(key) => key.unshield().ok(),
+        }
+    }
+}
+
+type KeyStore = SecureMutex<HashMap<String, ManagedKey>>;
+
 pub struct KeyManager {
-    keys: SecureMutex<HashMap<String, ClassifiedData<Vec<u8>>>>,
+    keys: KeyStore,
     rotation_interval: Duration,
+    storage: StorageMode,
 }
 
 impl KeyManager {
@@ -50,21 +128,62 @@ impl KeyManager {
         let manager = Self {
             keys: Arc::clone(&keys),
             rotation_interval,
+            storage: StorageMode::Plain,
         };
         manager.spawn_rotation_task(keys.clone());
         manager
     }
 
-    fn spawn_rotation_task(&self, keys: SecureMutex<HashMap<String, ClassifiedData<Vec<u8>>>>) {
+    /// Selects how keys accepted from now on are stored in memory.
+    pub fn with_storage(mut self, storage: StorageMode) -> Self {
+        self.storage = storage;
+        self
+    }
+
+    fn spawn_rotation_task(&self, keys: KeyStore) {
         let interval = self.rotation_interval;
 
         tokio::spawn(async move {
             loop {
                 tokio::time::sleep(interval).await;
-                let mut keys_lock = keys.lock().await;
-                for (id, key) in keys_lock.iter_mut() {
-                    // Replace this with actual keygen
-                    *key = ClassifiedData::new(vec![0u8; 32]);
+                // Generation can take seconds (RSA), so it runs on the blocking
+                // pool while the store stays unlocked.
+                let shapes: Vec<_> = keys
+                    .lock()
+                    .await
+                    .iter()
+                    .map(|(id, managed)| {
+                        (id.clone(), managed.algorithm.clone(), managed.key_length.clone())
+                    })
+                    .collect();
+
+                for (id, algorithm, key_length) in shapes {
+                    let (alg, len) = (algorithm.clone(), key_length.clone());
+                    let fresh = tokio::task::spawn_blocking(move || {
+                        KeyGenerator::generate(&alg, &len, true).map(take_key_material)
+                    })
+                    .await
+                    .unwrap_or_else(|e| Err(CryptoError::KeyGenerationError(e.to_string())));
+
+                    // Rotated keys keep their algorithm and length; a key that
+                    // cannot be regenerated stays in place until the next tick.
+                    let fresh = match fresh {
+                        Ok(fresh) => fresh,
+                        Err(_e) => {
+                            #[cfg(feature = "logging")]
+                            error!("Key {} rotation failed: {}", id, _e);
+                            continue;
+                        }
+                    };
+
+                    // Keys removed or replaced in the meantime are left alone.
+                    let mut keys_lock = keys.lock().await;
+                    let Some(managed) = keys_lock.get_mut(&id).filter(|managed| {
+                        managed.algorithm == algorithm && managed.key_length == key_length
+                    }) else {
+                        continue;
+                    };
+                    managed.key = StoredKey::from_classified(managed.key.mode(), fresh);
 
                     #[cfg(feature = "logging")]
                     info!("Key {} rotated.", id);
@@ -73,24 +192,33 @@ impl KeyManager {
         });
     }
 
-    pub async fn accept_key(&self, id: &str, length: usize, key: Vec<u8>) -> Result<(), SecureError> {
-        if length == 0 {
-            return Err(SecureError::InvalidKeyLength);
-        }
-        
-        if key.len() != length {
-            return Err(SecureError::InvalidKeyLength);
-        }
+    /// Stores `key` for `algorithm`, in the format [`CryptoPrimitive::new`]
+    /// accepts. Rotation replaces it with a fresh key of the same algorithm
+    /// and length.
+    pub async fn accept_key(
+        &self,
+        id: &str,
+        algorithm: &CryptoAlgorithm,
+        key: Vec<u8>,
+    ) -> Result<(), SecureError> {
+        let primitive = CryptoPrimitive::new(algorithm, key, true)
+            .map_err(|_| SecureError::InvalidKeyLength)?;
+        let managed = ManagedKey::new(self.storage, primitive);
 
-        let sensitive = ClassifiedData::new(key);
-
-        self.keys.lock().await.insert(id.to_string(), sensitive);
+        self.keys.lock().await.insert(id.to_string(), managed);
         Ok(())
     }
 
+    /// Returns a copy of the key; shielded keys are decrypted into the copy.
     pub async fn get_key(&self, id: &str) -> Option<ClassifiedData<Vec<u8>>> {
         let keys = self.keys.lock().await;
-        keys.get(id).cloned()
+        keys.get(id).and_then(|managed| managed.key.to_classified())
+    }
+
+    /// The algorithm the key was accepted for.
+    pub async fn key_algorithm(&self, id: &str) -> Option<CryptoAlgorithm> {
+        let keys = self.keys.lock().await;
+        keys.get(id).map(|managed| managed.algorithm.clone())
     }
 
     pub async fn remove_key(&self, id: &str) -> Result<(), SecureError> {
@@ -101,8 +229,10 @@ impl KeyManager {
 
     pub async fn compare_key(&self, id: &str, other: &[u8]) -> Option<bool> {
         let keys = self.keys.lock().await;
-        keys.get(id).map(|stored| {
-            stored.expose().ct_eq(other).into()
+        keys.get(id).and_then(|managed| {
+            managed
+                .key
+                .with_exposed(|key| key.as_slice().ct_eq(other).into())
         })
     }
 
@@ -111,6 +241,7 @@ impl KeyManager {
         let manager = Self {
             keys: Arc::clone(&keys),
             rotation_interval,
+            storage: StorageMode::Plain,
         };
         manager.spawn_rotation_task(keys.clone());
         manager
@@ -121,6 +252,114 @@ impl KeyManager {
         Self {
             keys,
             rotation_interval: Duration::from_secs(0),
+            storage: StorageMode::Plain,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs `f` on the stored key without copying it out, so these tests
+    /// also run under `no-clone`.
+    async fn with_key<R>(manager: &KeyManager, id: &str, f: impl FnOnce(&Vec<u8>) -> R) -> Option<R> {
+        manager.keys.lock().await.get(id)?.key.with_exposed(f)
+    }
+
+    #[tokio::test]
+    async fn shielded_storage_round_trips_keys() {
+        let manager = KeyManager::without_rotation().with_storage(StorageMode::Shielded);
+        manager
+            .accept_key("k1", &CryptoAlgorithm::AES, vec![4; 32])
+            .await
+            .unwrap();
+
+        let key = manager.get_key("k1").await.unwrap();
+        assert_eq!(key.expose(), &[4; 32]);
+        assert_eq!(manager.compare_key("k1", &[4; 32]).await, Some(true));
+        assert_eq!(manager.compare_key("k1", &[5; 32]).await, Some(false));
+    }
+
+    #[tokio::test]
+    async fn plain_storage_is_the_default() {
+        let manager = KeyManager::without_rotation();
+        manager
+            .accept_key("k1", &CryptoAlgorithm::ChaCha20Poly1305, vec![9; 32])
+            .await
+            .unwrap();
+        assert_eq!(manager.compare_key("k1", &[9; 32]).await, Some(true));
+        assert!(manager.get_key("missing").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn rejects_keys_that_do_not_fit_the_algorithm() {
+        let manager = KeyManager::without_rotation();
+        assert!(matches!(
+            manager
+                .accept_key("k1", &CryptoAlgorithm::AES, vec![9; 16])
+                .await,
+            Err(SecureError::InvalidKeyLength)
+        ));
+        assert!(
+            manager
+                .accept_key("k2", &CryptoAlgorithm::RSA, vec![9; 256])
+                .await
+                .is_err()
+        );
+        assert!(manager.get_key("k1").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn rotation_draws_fresh_keys_of_the_same_length() {
+        for storage in [StorageMode::Plain, StorageMode::Shielded] {
+            let manager =
+                KeyManager::with_rotation(Duration::from_millis(20)).with_storage(storage);
+            manager
+                .accept_key("k1", &CryptoAlgorithm::EcdsaP384, vec![1u8; 48])
+                .await
+                .unwrap();
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            assert_eq!(with_key(&manager, "k1", Vec::len).await, Some(48));
+            assert_eq!(manager.compare_key("k1", &[1u8; 48]).await, Some(false));
+            assert_eq!(
+                manager.key_algorithm("k1").await,
+                Some(CryptoAlgorithm::EcdsaP384)
+            );
+        }
+    }
+
+    #[cfg(feature = "rsa")]
+    #[tokio::test]
+    async fn rotated_rsa_keys_stay_valid_der() {
+        use secrecy::ExposeSecret;
+
+        let original =
+            KeyGenerator::generate(&CryptoAlgorithm::RSA, &KeyLength::Bits2048, true).unwrap();
+        let original = original.key_material.expose_secret().clone();
+
+        let manager = KeyManager::with_rotation(Duration::from_millis(20));
+        manager
+            .accept_key("rsa", &CryptoAlgorithm::RSA, original.clone())
+            .await
+            .unwrap();
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(30);
+        while manager.compare_key("rsa", &original).await == Some(true) {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "key was never rotated"
+            );
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        let primitive = with_key(&manager, "rsa", |der| {
+            CryptoPrimitive::new(&CryptoAlgorithm::RSA, der.clone(), true)
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(primitive.key_length, KeyLength::Bits2048);
+    }
+}
@@ -28,6 +28,8 @@
 
 
 use crate::{
+    access_control::AccessChecker,
+    classification::{Clearance, Label},
     errors::SecureError,
     traits::{
         Frame,
@@ -35,34 +37,78 @@ use crate::{
     }
 };
 
+struct Layer {
+    handler: Box<dyn FramedLayerHandler>,
+    clearance: Option<Clearance>,
+}
+
+/// Runs a frame through a sequence of layers.
+///
+/// With an [`AccessChecker`] attached, every hop is checked against the
+/// frame's label, taken from its data and never lower than
+/// `metadata.label`: a layer may not receive a frame its clearance does not
+/// dominate, layers pushed without a clearance receive nothing, and no layer
+/// may return a frame labelled lower than the one it received.
 pub struct LayerStack {
-    layers: Vec<Box<dyn FramedLayerHandler>>,
+    layers: Vec<Layer>,
+    checker: Option<AccessChecker>,
 }
 
 impl LayerStack {
     pub fn new() -> Self {
-        Self { layers: vec![] }
+        Self { layers: vec![], checker: None }
+    }
+
+    /// Check labels with `checker` while forwarding frames.
+    pub fn with_access_checker(mut self, checker: AccessChecker) -> Self {
+        self.checker = Some(checker);
+        self
     }
 
     pub fn push<L: FramedLayerHandler + 'static>(&mut self, layer: L) {
-        self.layers.push(Box::new(layer));
+        self.layers.push(Layer { handler: Box::new(layer), clearance: None });
+    }
+
+    /// Push a layer that may only read frames `clearance` dominates.
+    pub fn push_cleared<L: FramedLayerHandler + 'static>(&mut self, layer: L, clearance: Clearance) {
+        self.layers.push(Layer { handler: Box::new(layer), clearance: Some(clearance) });
     }
 
     pub async fn run(&self, frame: Frame) -> Result<Frame, SecureError> {
         let mut current = frame;
         for layer in &self.layers {
-            current = layer.handle(current).await?;
+            let Some(checker) = &self.checker else {
+                current = layer.handler.handle(current).await?;
+                continue;
+            };
+
+            let input = effective_label(&current);
+            match &layer.clearance {
+                Some(clearance) => checker.check_read(clearance, &input)?,
+                None => checker.check_read_uncleared(&input)?,
+            }
+            current = layer.handler.handle(current).await?;
+
+            let output = effective_label(&current);
+            checker.check_write(&input, &output)?;
+            current.metadata.label = output;
         }
         Ok(current)
     }
 }
 
+/// The label carried by the frame's data. `metadata.label` is a public field
+/// any layer may edit, so it can only raise the label, never lower it.
+fn effective_label(frame: &Frame) -> Label {
+    frame.data.label().join(&frame.metadata.label)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::traits::{Frame, FramedLayerHandler};
     use crate::classified_data::ClassifiedData;
-    use crate::errors::SecureError;
+    use crate::errors::{AccessError, SecureError};
     use async_trait::async_trait;
 
     #[derive(Clone)]
@@ -89,9 +135,30 @@ mod tests {
     }
 
     fn frame_with(data: &[u8]) -> Frame {
-        Frame {
-            data: ClassifiedData::new(data.to_vec()),
-            metadata: None,
+        Frame::new(ClassifiedData::new(data.to_vec()))
+    }
+
+    #[derive(Clone)]
+    struct StripCompartmentsLayer;
+
+    #[async_trait]
+    impl FramedLayerHandler for StripCompartmentsLayer {
+        async fn handle(&self, mut frame: Frame) -> Result<Frame, SecureError> {
+            frame.data = ClassifiedData::new(frame.data.expose().clone());
+            frame.metadata.label.compartments.clear();
+            Ok(frame)
+        }
+    }
+
+    /// Only edits the metadata, leaving the data's compartments in place.
+    #[derive(Clone)]
+    struct RelabelLayer;
+
+    #[async_trait]
+    impl FramedLayerHandler for RelabelLayer {
+        async fn handle(&self, mut frame: Frame) -> Result<Frame, SecureError> {
+            frame.metadata.label.compartments.clear();
+            Ok(frame)
         }
     }
 
@@ -119,6 +186,64 @@ mod tests {
         assert_eq!(result.data.expose(), b"start123");
     }
 
+    #[tokio::test]
+    async fn test_layer_stack_refuses_read_up() {
+        use crate::classification::Classification;
+
+        let mut stack = LayerStack::new().with_access_checker(AccessChecker::new());
+        stack.push_cleared(AppendLayer(b"x"), Clearance::new(Classification::Secret));
+
+        let pii = Frame::new(ClassifiedData::new(b"ssn".to_vec()).with_compartments(["PII"]));
+        let result = stack.run(pii).await;
+        assert!(matches!(result.unwrap_err(), SecureError::AccessDenied(_)));
+
+        let plain = stack.run(frame_with(b"ok")).await.unwrap();
+        assert_eq!(plain.data.expose(), b"okx");
+    }
+
+    #[tokio::test]
+    async fn test_layer_stack_refuses_write_down() {
+        use crate::classification::Classification;
+
+        let mut stack = LayerStack::new().with_access_checker(AccessChecker::new());
+        stack.push_cleared(
+            StripCompartmentsLayer,
+            Clearance::new(Classification::TopSecret).with("PII"),
+        );
+
+        let pii = Frame::new(ClassifiedData::new(b"ssn".to_vec()).with_compartments(["PII"]));
+        assert!(matches!(
+            stack.run(pii).await.unwrap_err(),
+            SecureError::AccessDenied(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_layer_stack_labels_come_from_data() {
+        use crate::classification::Classification;
+
+        let mut stack = LayerStack::new().with_access_checker(AccessChecker::new());
+        stack.push_cleared(RelabelLayer, Clearance::new(Classification::TopSecret).with("PII"));
+        stack.push_cleared(AppendLayer(b"x"), Clearance::new(Classification::TopSecret));
+
+        let pii = Frame::new(ClassifiedData::new(b"ssn".to_vec()).with_compartments(["PII"]));
+        assert!(matches!(
+            stack.run(pii).await.unwrap_err(),
+            SecureError::AccessDenied(AccessError::ReadUp { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_layer_stack_without_clearance_fails_closed() {
+        let mut stack = LayerStack::new().with_access_checker(AccessChecker::new());
+        stack.push(AppendLayer(b"x"));
+
+        assert!(matches!(
+            stack.run(frame_with(b"data")).await.unwrap_err(),
+            SecureError::AccessDenied(AccessError::NoClearance { .. })
+        ));
+    }
+
     #[tokio::test]
     async fn test_layer_stack_with_failure() {
         let mut stack = LayerStack::new();
@@ -11,16 +11,31 @@
 //! * **Isolation:** Opaque type wrappers to prevent accidental logging or exposure.
 //! * **Resistance:** Constant-time operations via `subtle` to mitigate timing attacks.
 //! * **Integrity:** Type-safe boundaries between "Public" and "Classified" data.
+//!
+//! ## RSA
+//! RSA-OAEP and RSA-PSS are only available with the opt-in `rsa` feature.
+//! The `rsa` crate they rely on is affected by
+//! [RUSTSEC-2023-0071](https://rustsec.org/advisories/RUSTSEC-2023-0071)
+//! (the Marvin attack): its private-key operations are not constant-time,
+//! even with blinding, so timing many decryptions or signatures can leak the
+//! key. Keep RSA decryption away from network-facing paths; without the
+//! feature, RSA operations fail with
+//! [`CryptoError::RSADisabled`](crate::errors::CryptoError::RSADisabled).
 
 pub use zeroize;
 pub use subtle;
 pub use secrecy;
 
+pub mod classification;
 pub mod classified_data;
+pub mod declassification;
 pub mod errors;
 pub mod exposure_aware_classified_data;
+pub mod gf;
 pub mod classified_map;
+pub mod scratch;
 pub mod sized_classified_data;
+pub mod shielded_classified_data;
 pub mod traits;
 
 
@@ -31,6 +46,11 @@ pub mod config;
 
 pub mod zeroizing_guard;
 
+#[cfg(unix)]
+pub mod secure_memory;
+#[cfg(unix)]
+pub mod locked_classified_data;
+
 pub mod pipelines;
 #[macro_use]
 pub mod macros;
@@ -38,19 +58,33 @@ pub mod macros;
 
 
 
+#[cfg(feature = "async")]
+pub mod access_control;
 #[cfg(feature = "async")]
 pub mod async_classified_data;
 #[cfg(feature = "async")]
+pub mod classified_buffer;
+#[cfg(feature = "async")]
+pub mod classified_string;
+#[cfg(feature = "async")]
 pub mod concurrency;
 #[cfg(feature = "async")]
+pub mod constant_time;
+#[cfg(feature = "async")]
 pub mod crypto_fallback;
 #[cfg(feature = "async")]
 pub mod data_repository;
 #[cfg(feature = "async")]
+pub mod ephemeral_classified_data;
+#[cfg(feature = "async")]
 pub mod key_manager;
 #[cfg(feature = "async")]
 pub mod layer_stack;
 #[cfg(feature = "async")]
+pub mod secret_provider;
+#[cfg(feature = "async")]
 pub mod secure_service;
 #[cfg(feature = "async")]
+pub mod shamir;
+#[cfg(feature = "async")]
 pub mod thread_pool_manager;
\ No newline at end of file
@@ -0,0 +1,164 @@
+//! ----------------------------------------------
+//! DOCUMENT DETAILS -----------------------------
+//!
+//! filename:locked_classified_data.rs
+//! description:ClassifiedData variants backed by page-locked and guarded memory
+//! usages:none in crate yet
+//!
+//! ----------------------------------------------
+//! FEATURE NOTES --------------------------------
+//!
+//! feature_name:async
+//! deps:[tokio][async_trait]
+//! scope:[impl_secure_classified!]
+//! corpus:true
+//!
+//! feature_name:std
+//! deps:[std][libc]
+//! scope:[struct LockedClassifiedData][struct GuardedClassifiedData]
+//! corpus:false
+//!
+//! ----------------------------------------------
+//! CORPUS FEATURES ------------------------------
+//!
+#![cfg(feature = "async")]
+#![cfg(feature = "std")]
+#![cfg(unix)]
+
+use std::io;
+
+use secrecy::ExposeSecret;
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+use crate::{
+    impl_secure_classified,
+    secure_memory::{GuardedBox, LockedBox},
+};
+
+/// A classified value stored on `mlock`ed pages.
+///
+/// Behaves like [`ClassifiedData<T>`](crate::classified_data::ClassifiedData)
+/// but the secret never reaches swap or a core dump, and its pages are wiped
+/// and unlocked on drop. See [`LockedBox`] for the exact guarantees.
+///
+/// # Example
+/// ```
+/// use classified::locked_classified_data::LockedClassifiedData;
+/// let key = LockedClassifiedData::new([0x42u8; 32]);
+/// assert_eq!(key.expose()[0], 0x42);
+/// ```
+pub struct LockedClassifiedData<T: Zeroize> {
+    data: LockedBox<T>,
+}
+
+impl<T: Zeroize> LockedClassifiedData<T> {
+    /// Create a new classified value on locked pages.
+    ///
+    /// # Panics
+    /// Panics if the memory cannot be locked; see [`LockedClassifiedData::try_new`].
+    pub fn new(data: T) -> Self {
+        Self {
+            data: LockedBox::new(data),
+        }
+    }
+
+    /// Create a new classified value on locked pages, reporting allocation
+    /// or `mlock` failures instead of panicking.
+    pub fn try_new(data: T) -> io::Result<Self> {
+        Ok(Self {
+            data: LockedBox::try_new(data)?,
+        })
+    }
+}
+
+impl_secure_classified!(LockedClassifiedData);
+
+/// A classified value on locked pages fenced by guard pages and a canary.
+///
+/// Reading or writing past either end of the secret faults instead of
+/// silently touching neighbouring memory, and a corrupted canary aborts the
+/// process when the value is dropped. See [`GuardedBox`] for the layout.
+///
+/// # Example
+/// ```
+/// use classified::locked_classified_data::GuardedClassifiedData;
+/// let key = GuardedClassifiedData::new([0x42u8; 32]);
+/// assert_eq!(key.expose()[31], 0x42);
+/// ```
+pub struct GuardedClassifiedData<T: Zeroize> {
+    data: GuardedBox<T>,
+}
+
+impl<T: Zeroize> GuardedClassifiedData<T> {
+    /// Create a new classified value between guard pages.
+    ///
+    /// # Panics
+    /// Panics if the memory cannot be mapped or locked; see
+    /// [`GuardedClassifiedData::try_new`].
+    pub fn new(data: T) -> Self {
+        Self {
+            data: GuardedBox::new(data),
+        }
+    }
+
+    /// Create a new classified value between guard pages, reporting
+    /// allocation or `mlock` failures instead of panicking.
+    pub fn try_new(data: T) -> io::Result<Self> {
+        Ok(Self {
+            data: GuardedBox::try_new(data)?,
+        })
+    }
+}
+
+impl_secure_classified!(GuardedClassifiedData);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ClassifiedEq;
+
+    #[test]
+    fn creates_and_exposes_data() {
+        let secret = LockedClassifiedData::new([5u8; 32]);
+        assert_eq!(secret.expose(), &[5u8; 32]);
+        assert_eq!(secret[0], 5);
+    }
+
+    #[cfg(not(feature = "no-clone"))]
+    #[test]
+    fn clone_lands_on_its_own_pages() {
+        let secret = LockedClassifiedData::new([1u8; 16]);
+        let cloned = secret.clone();
+        assert_eq!(secret.expose(), cloned.expose());
+        assert_ne!(secret.expose().as_ptr(), cloned.expose().as_ptr());
+    }
+
+    #[test]
+    fn constant_time_eq_for_byte_vectors() {
+        let a = LockedClassifiedData::new(vec![1u8, 2, 3]);
+        let b = LockedClassifiedData::new(vec![1u8, 2, 3]);
+        let c = LockedClassifiedData::new(vec![3u8, 2, 1]);
+
+        assert!(bool::from(a.ct_eq(&b)));
+        assert!(!a.classified_eq(&c));
+    }
+
+    #[cfg(not(feature = "no-clone"))]
+    #[test]
+    fn guarded_clone_stays_guarded() {
+        let secret = GuardedClassifiedData::new([9u8; 32]);
+        let cloned = secret.clone();
+        assert_eq!(cloned.expose(), &[9u8; 32]);
+        assert!(cloned.data.canary_intact());
+    }
+
+    #[test]
+    fn debug_is_redacted() {
+        let secret = LockedClassifiedData::new([0u8; 4]);
+        assert_eq!(format!("{:?}", secret), "LockedClassifiedData(<REDACTED>)");
+
+        let guarded = GuardedClassifiedData::new([0u8; 4]);
+        assert_eq!(format!("{:?}", guarded), "GuardedClassifiedData(<REDACTED>)");
+    }
+}
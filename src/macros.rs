@@ -126,7 +126,7 @@ macro_rules! impl_clone {
         {
             fn clone(&self) -> Self {
                 $type_name {
-                    data: $crate::secrecy::SecretBox::new(
+                    data: ::core::convert::From::from(
                         Box::new(self.data.expose_secret().clone())
                     ),
                 }
@@ -173,14 +173,158 @@ macro_rules! impl_ct {
     // Default label = the type name
     ($type_name:ident) => {
         impl $crate::subtle::ConstantTimeEq for $type_name<Vec<u8>> {
+            // Reads the storage directly. Wrappers whose reads are rationed
+            // by a policy implement `ConstantTimeEq` themselves instead.
             fn ct_eq(&self, other: &Self) -> $crate::subtle::Choice {
-                self.expose().ct_eq(other.expose())
+                $crate::secrecy::ExposeSecret::expose_secret(&self.data)
+                    .ct_eq($crate::secrecy::ExposeSecret::expose_secret(&other.data))
             }
         }
     };
 }
 
 
+/// Implements [`ConstantTimeOps`](crate::constant_time::ConstantTimeOps)
+/// for a byte-holding wrapper.
+///
+/// - `impl_ct_ops!(Type, ctor)` for wrappers over `data: SecretBox<Vec<u8>>`,
+///   with an optional extra generic after the constructor, e.g. `L: Level`,
+///   and an optional `; labels = field` naming a `BTreeSet` of compartments.
+///   Selected and swapped values carry the union of both inputs' labels.
+/// - `impl_ct_ops!(plain Type)` for wrappers over a bare `data: T`, covering
+///   both `Vec<u8>` and `[u8; N]`.
+/// - `impl_ct_ops!(audited Type)` for wrappers over `data: SecretBox<Vec<u8>>`
+///   whose `try_expose` enforces and records an exposure policy. Every read
+///   goes through it and panics if refused, like `expose`. Both inputs of a
+///   select or swap must have equal `policy()`s; selected values are built
+///   with the wrapper's `derive`.
+#[macro_export]
+macro_rules! impl_ct_ops {
+    ($type_name:ident, $ctor:path $(, $gen:ident: $bound:path)? $(; labels = $labels:ident)?) => {
+        impl$(<$gen: $bound>)? $crate::constant_time::sealed::ConstantTimeBytes
+            for $type_name<Vec<u8> $(, $gen)?>
+        {
+            fn ct_bytes(&self) -> &[u8] {
+                $crate::secrecy::ExposeSecret::expose_secret(&self.data)
+            }
+
+            fn ct_bytes_mut(&mut self) -> &mut [u8] {
+                $crate::secrecy::ExposeSecretMut::expose_secret_mut(&mut self.data)
+            }
+
+            fn from_ct_bytes(
+                bytes: Vec<u8>,
+            ) -> ::core::result::Result<Self, $crate::errors::EncodingError> {
+                ::core::result::Result::Ok($ctor(bytes))
+            }
+
+            $(
+            fn ct_select_from(
+                a: &Self,
+                b: &Self,
+                bytes: Vec<u8>,
+            ) -> ::core::result::Result<Self, $crate::errors::EncodingError> {
+                let mut selected = $ctor(bytes);
+                selected.$labels = a.$labels.union(&b.$labels).cloned().collect();
+                ::core::result::Result::Ok(selected)
+            }
+
+            fn ct_before_swap(&mut self, other: &mut Self) {
+                let joined: ::std::collections::BTreeSet<_> =
+                    self.$labels.union(&other.$labels).cloned().collect();
+                self.$labels.clone_from(&joined);
+                other.$labels = joined;
+            }
+            )?
+        }
+
+        impl$(<$gen: $bound>)? $crate::constant_time::ConstantTimeOps
+            for $type_name<Vec<u8> $(, $gen)?> {}
+    };
+
+    (audited $type_name:ident) => {
+        impl $crate::constant_time::sealed::ConstantTimeBytes for $type_name<Vec<u8>> {
+            fn ct_bytes(&self) -> &[u8] {
+                match self.try_expose() {
+                    ::core::result::Result::Ok(data) => data,
+                    ::core::result::Result::Err(e) => ::core::panic!("exposure refused by policy: {e}"),
+                }
+            }
+
+            fn ct_bytes_mut(&mut self) -> &mut [u8] {
+                if let ::core::result::Result::Err(e) = self.try_expose() {
+                    ::core::panic!("exposure refused by policy: {e}");
+                }
+                $crate::secrecy::ExposeSecretMut::expose_secret_mut(&mut self.data)
+            }
+
+            fn from_ct_bytes(
+                bytes: Vec<u8>,
+            ) -> ::core::result::Result<Self, $crate::errors::EncodingError> {
+                ::core::result::Result::Ok(Self::new(bytes))
+            }
+
+            fn ct_select_from(
+                a: &Self,
+                b: &Self,
+                bytes: Vec<u8>,
+            ) -> ::core::result::Result<Self, $crate::errors::EncodingError> {
+                ::core::assert!(a.policy() == b.policy(), "conditional select needs equal policies");
+                ::core::result::Result::Ok(a.derive(bytes))
+            }
+
+            fn ct_before_swap(&mut self, other: &mut Self) {
+                ::core::assert!(self.policy() == other.policy(), "conditional swap needs equal policies");
+            }
+        }
+
+        impl $crate::constant_time::ConstantTimeOps for $type_name<Vec<u8>> {}
+    };
+
+    (plain $type_name:ident) => {
+        impl $crate::constant_time::sealed::ConstantTimeBytes for $type_name<Vec<u8>> {
+            fn ct_bytes(&self) -> &[u8] {
+                &self.data
+            }
+
+            fn ct_bytes_mut(&mut self) -> &mut [u8] {
+                &mut self.data
+            }
+
+            fn from_ct_bytes(
+                bytes: Vec<u8>,
+            ) -> ::core::result::Result<Self, $crate::errors::EncodingError> {
+                ::core::result::Result::Ok(Self::new(bytes))
+            }
+        }
+
+        impl<const N: usize> $crate::constant_time::sealed::ConstantTimeBytes for $type_name<[u8; N]> {
+            fn ct_bytes(&self) -> &[u8] {
+                &self.data
+            }
+
+            fn ct_bytes_mut(&mut self) -> &mut [u8] {
+                &mut self.data
+            }
+
+            fn from_ct_bytes(
+                mut bytes: Vec<u8>,
+            ) -> ::core::result::Result<Self, $crate::errors::EncodingError> {
+                let array: ::core::result::Result<[u8; N], _> = bytes.as_slice().try_into();
+                let len = bytes.len();
+                $crate::zeroize::Zeroize::zeroize(&mut bytes);
+                array
+                    .map(Self::new)
+                    .map_err(|_| $crate::errors::EncodingError::InvalidLength(len))
+            }
+        }
+
+        impl $crate::constant_time::ConstantTimeOps for $type_name<Vec<u8>> {}
+        impl<const N: usize> $crate::constant_time::ConstantTimeOps for $type_name<[u8; N]> {}
+    };
+}
+
+
 /// Implements secure memory and ergonomic trait impls for secret types.
 ///
 /// Usage:
@@ -24,8 +24,8 @@
 #![cfg(feature = "async")]
 
 use crate::config::{PipelineConfig, StageConfig};
-use crate::crypto::crypto_algorithm::CryptoAlgorithm;
 use crate::crypto::crypto_primitive::CryptoPrimitive;
+use crate::crypto::helpers::parse_algorithm;
 use crate::errors::SecureError;
 use crate::classified_data::ClassifiedData;
 // use crate::pipeline_builder::{PipelineStage, create_actor, create_stream, create_future, create_csp};
@@ -80,18 +80,7 @@ impl Pipeline {
 // }
 
 pub fn create_actor(config: &StageConfig) -> Result<impl PipelineStage, SecureError> {
-    let algorithm_str = config.algorithm
-        .clone()
-        .ok_or_else(|| SecureError::PipelineError(
-            "algorithm".to_owned()
-        ))?;
-
-    let algorithm = match algorithm_str.as_str() {
-        "AES" => CryptoAlgorithm::AES,
-        "RSA" => CryptoAlgorithm::RSA,
-        "ECDSA" => CryptoAlgorithm::ECDSA,
-        other => return Err(SecureError::PipelineError(format!("Unsupported algorithm: {}", other))),
-    };
+    let algorithm = parse_algorithm(&config.algorithm)?;
 
     let key_material = config.key_material.clone()
         .ok_or_else(|| SecureError::PipelineError(
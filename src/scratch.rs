@@ -0,0 +1,170 @@
+//! ----------------------------------------------
+//! DOCUMENT DETAILS -----------------------------
+//!
+//! filename:scratch.rs
+//! description:arena for intermediates derived from secrets
+//! usages:[classified_data.rs]
+//!
+//! ----------------------------------------------
+//! FEATURE NOTES --------------------------------
+//!
+//! feature_name:none
+//! deps:[zeroize]
+//! scope:[struct Scratch]
+//! corpus:true
+//!
+//! ----------------------------------------------
+//! CORPUS FEATURES ------------------------------
+//!
+
+//! Wiped scratch space for secret transformations.
+//!
+//! Deriving one secret from another usually produces intermediates: a
+//! decoded buffer, a concatenation, a half-finished digest. Allocating them
+//! in a [`Scratch`] hands them to an arena that zeroizes every one of them
+//! when the transformation ends, whether it succeeds or not.
+
+use std::{fmt, ptr::NonNull, sync::Mutex};
+
+use zeroize::Zeroize;
+
+/// An arena whose allocations are zeroized together when it is dropped.
+///
+/// Passed to the closures of
+/// [`ClassifiedData::map`](crate::classified_data::ClassifiedData::map) and
+/// friends.
+///
+/// # Example
+/// ```
+/// use classified::scratch::Scratch;
+///
+/// let scratch = Scratch::new();
+/// let joined = scratch.bytes(8);
+/// joined[..4].copy_from_slice(b"salt");
+/// let copy = scratch.keep(joined.to_vec());
+/// assert_eq!(&copy[..4], b"salt");
+/// // Both buffers are wiped here.
+/// ```
+#[derive(Default)]
+pub struct Scratch {
+    items: Mutex<Vec<Kept>>,
+}
+
+/// An allocation owned by the arena, held as a raw pointer so the `&mut`
+/// borrows handed out by [`Scratch::keep`] stay valid as the list grows.
+struct Kept(NonNull<dyn Zeroize + Send>);
+
+// SAFETY: `Kept` uniquely owns a `Send` value, like the box it came from.
+unsafe impl Send for Kept {}
+
+impl Scratch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Move `value` into the arena and borrow it for the arena's lifetime.
+    ///
+    /// The value is zeroized and dropped together with the arena.
+    #[allow(clippy::mut_from_ref)]
+    pub fn keep<V: Zeroize + Send + 'static>(&self, value: V) -> &mut V {
+        let ptr = NonNull::from(Box::leak(Box::new(value)));
+        self.items
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(Kept(ptr));
+        // SAFETY: the allocation is only reclaimed when the arena is dropped,
+        // which needs every borrow to have ended. The arena never reads it
+        // before then, and each call leaks a new box, so the returned
+        // references never alias.
+        unsafe { &mut *ptr.as_ptr() }
+    }
+
+    /// Allocate a zero-filled byte buffer that is wiped with the arena.
+    #[allow(clippy::mut_from_ref)]
+    pub fn bytes(&self, len: usize) -> &mut [u8] {
+        self.keep(vec![0u8; len]).as_mut_slice()
+    }
+
+    /// Number of allocations held by the arena.
+    pub fn len(&self) -> usize {
+        self.items.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let items = self.items.get_mut().unwrap_or_else(|e| e.into_inner());
+        for Kept(ptr) in items.drain(..) {
+            // SAFETY: `ptr` came from `Box::leak` in `keep` and is reclaimed
+            // exactly once, after every borrow of it has ended.
+            let mut item = unsafe { Box::from_raw(ptr.as_ptr()) };
+            item.zeroize();
+        }
+    }
+}
+
+impl fmt::Debug for Scratch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Scratch({} allocations, <REDACTED>)", self.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+
+    struct Flag(Arc<AtomicBool>);
+
+    impl Zeroize for Flag {
+        fn zeroize(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn allocations_are_independent() {
+        let scratch = Scratch::new();
+        let a = scratch.bytes(4);
+        let b = scratch.bytes(4);
+        a.copy_from_slice(b"aaaa");
+        b.copy_from_slice(b"bbbb");
+        assert_eq!(a, b"aaaa");
+        assert_eq!(scratch.len(), 2);
+    }
+
+    #[test]
+    fn drop_zeroizes_everything() {
+        let wiped = Arc::new(AtomicBool::new(false));
+        {
+            let scratch = Scratch::new();
+            scratch.keep(Flag(Arc::clone(&wiped)));
+        }
+        assert!(wiped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn earlier_borrows_survive_later_allocations() {
+        let scratch = Scratch::new();
+        let first = scratch.keep(vec![1u8; 4]);
+        for _ in 0..64 {
+            scratch.bytes(16);
+        }
+        first.push(5);
+        assert_eq!(first, &[1, 1, 1, 1, 5]);
+    }
+
+    #[test]
+    fn debug_is_redacted() {
+        let scratch = Scratch::new();
+        scratch.bytes(1)[0] = 42;
+        assert_eq!(format!("{:?}", scratch), "Scratch(1 allocations, <REDACTED>)");
+    }
+}
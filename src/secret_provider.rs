@@ -0,0 +1,577 @@
+//! ----------------------------------------------
+//! DOCUMENT DETAILS -----------------------------
+//!
+//! filename:secret_provider.rs
+//! description:pluggable secret sources and a background refresher
+//! usages:none in crate yet
+//!
+//! ----------------------------------------------
+//! FEATURE NOTES --------------------------------
+//!
+//! feature_name:async
+//! deps:[tokio][async_trait]
+//! scope:[trait SecretProvider][struct SecretRefresher]
+//! corpus:true
+//!
+//! feature_name:logging
+//! deps:[tracing]
+//! scope:[fn refresh_loop]
+//! corpus:false
+//!
+//! feature_name:std
+//! deps:[std]
+//! scope:[struct EnvProvider][struct FileProvider][struct CommandProvider]
+//! corpus:false
+//!
+//! ----------------------------------------------
+//! CORPUS FEATURES ------------------------------
+//!
+#![cfg(feature = "async")]
+#![cfg(feature = "std")]
+
+//! Secrets that keep themselves current.
+//!
+//! A [`SecretProvider`] knows how to fetch a secret and, optionally, when it
+//! expires. A [`SecretRefresher`] fetches it once, then keeps an
+//! [`AsyncClassifiedData`] up to date in the background:
+//!
+//! - it refreshes ahead of the expiry, or on a fixed interval when the
+//!   provider reports none;
+//! - on failure it retries with exponential backoff and jitter;
+//! - while retrying it keeps serving the last good value, and flags it as
+//!   stale once that value has expired.
+//!
+//! Consumers read the value through [`RefreshingSecret::value`] and can
+//! [`subscribe`](AsyncClassifiedData::subscribe) to rotations.
+
+use std::{
+    ffi::OsString,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, SystemTime},
+};
+
+use async_trait::async_trait;
+use tokio::task::JoinHandle;
+use zeroize::Zeroize;
+
+#[cfg(feature = "logging")]
+use tracing::warn;
+
+use crate::{
+    async_classified_data::AsyncClassifiedData, classified_data::ClassifiedData,
+    errors::ProviderError,
+};
+
+/// A secret returned by a [`SecretProvider`], with its expiry if known.
+pub struct FetchedSecret<T: Zeroize> {
+    pub value: ClassifiedData<T>,
+    pub expires_at: Option<SystemTime>,
+}
+
+impl<T: Zeroize> FetchedSecret<T> {
+    /// A secret without a known expiry.
+    pub fn new(value: T) -> Self {
+        Self {
+            value: ClassifiedData::new(value),
+            expires_at: None,
+        }
+    }
+
+    /// Set the instant after which the secret must not be used.
+    pub fn expires_at(mut self, at: SystemTime) -> Self {
+        self.expires_at = Some(at);
+        self
+    }
+}
+
+/// A source a secret can be (re)fetched from.
+#[async_trait]
+pub trait SecretProvider<T: Zeroize>: Send + Sync {
+    async fn fetch(&self) -> Result<FetchedSecret<T>, ProviderError>;
+}
+
+/// Keeps an [`AsyncClassifiedData`] in sync with a [`SecretProvider`].
+///
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+/// use classified::secret_provider::{FileProvider, SecretRefresher};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), classified::errors::ProviderError> {
+/// let token = SecretRefresher::new(FileProvider::new("/run/secrets/api_token"))
+///     .refresh_interval(Duration::from_secs(60))
+///     .start()
+///     .await?;
+///
+/// let len = token.value().with(|t| t.len()).await;
+/// # let _ = len;
+/// # Ok(())
+/// # }
+/// ```
+pub struct SecretRefresher<P> {
+    provider: P,
+    refresh_ahead: Duration,
+    refresh_interval: Duration,
+    min_refresh_delay: Duration,
+    min_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl<P> SecretRefresher<P> {
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            refresh_ahead: Duration::from_secs(30),
+            refresh_interval: Duration::from_secs(300),
+            min_refresh_delay: Duration::from_secs(1),
+            min_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+
+    /// How long before the reported expiry to fetch a replacement.
+    pub fn refresh_ahead(mut self, ahead: Duration) -> Self {
+        self.refresh_ahead = ahead;
+        self
+    }
+
+    /// How often to refresh secrets that report no expiry.
+    pub fn refresh_interval(mut self, interval: Duration) -> Self {
+        self.refresh_interval = interval;
+        self
+    }
+
+    /// Lower bound on the wait between two successful refreshes.
+    ///
+    /// Guards the provider when it hands out secrets whose TTL is shorter
+    /// than [`refresh_ahead`](Self::refresh_ahead) or that are already
+    /// expired.
+    pub fn min_refresh_delay(mut self, delay: Duration) -> Self {
+        self.min_refresh_delay = delay;
+        self
+    }
+
+    /// Bounds for the exponential backoff between failed attempts.
+    pub fn backoff(mut self, min: Duration, max: Duration) -> Self {
+        self.min_backoff = min;
+        self.max_backoff = max.max(min);
+        self
+    }
+
+    /// Fetch the secret once and start refreshing it in the background.
+    ///
+    /// Fails if the initial fetch fails; there is no last good value to serve
+    /// yet. Must be called from within a tokio runtime.
+    pub async fn start<T>(self) -> Result<RefreshingSecret<T>, ProviderError>
+    where
+        P: SecretProvider<T> + 'static,
+        T: Zeroize + Send + Sync + 'static,
+    {
+        let first = self.provider.fetch().await?;
+        let value = AsyncClassifiedData::new(first.value.into_value());
+        let state = Arc::new(RefreshState {
+            stale: AtomicBool::new(false),
+            expires_at: Mutex::new(first.expires_at),
+        });
+
+        let task = tokio::spawn(refresh_loop(self, value.clone(), Arc::clone(&state)));
+        Ok(RefreshingSecret { value, state, task })
+    }
+}
+
+struct RefreshState {
+    stale: AtomicBool,
+    expires_at: Mutex<Option<SystemTime>>,
+}
+
+impl RefreshState {
+    fn expires_at(&self) -> Option<SystemTime> {
+        *self.expires_at.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn set_expires_at(&self, at: Option<SystemTime>) {
+        *self.expires_at.lock().unwrap_or_else(|e| e.into_inner()) = at;
+    }
+}
+
+/// A secret kept current by a [`SecretRefresher`].
+///
+/// Dropping it stops the background refresh.
+pub struct RefreshingSecret<T: Zeroize> {
+    value: AsyncClassifiedData<T>,
+    state: Arc<RefreshState>,
+    task: JoinHandle<()>,
+}
+
+impl<T: Zeroize> RefreshingSecret<T> {
+    /// The live value. Clones share storage, so they see every refresh.
+    pub fn value(&self) -> &AsyncClassifiedData<T> {
+        &self.value
+    }
+
+    /// Whether the last refresh failed and the served value has expired.
+    pub fn is_stale(&self) -> bool {
+        self.state.stale.load(Ordering::SeqCst)
+    }
+
+    /// Expiry reported by the provider for the served value.
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        self.state.expires_at()
+    }
+}
+
+impl<T: Zeroize> Drop for RefreshingSecret<T> {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn refresh_loop<P, T>(
+    refresher: SecretRefresher<P>,
+    value: AsyncClassifiedData<T>,
+    state: Arc<RefreshState>,
+) where
+    P: SecretProvider<T>,
+    T: Zeroize + Send + Sync,
+{
+    loop {
+        tokio::time::sleep(next_refresh_in(&refresher, state.expires_at())).await;
+
+        let mut backoff = refresher.min_backoff;
+        loop {
+            match refresher.provider.fetch().await {
+                Ok(fresh) => {
+                    value
+                        .update(|v| {
+                            v.zeroize();
+                            *v = fresh.value.into_value();
+                        })
+                        .await;
+                    state.set_expires_at(fresh.expires_at);
+                    state.stale.store(false, Ordering::SeqCst);
+                    break;
+                }
+                Err(_e) => {
+                    let expired = state
+                        .expires_at()
+                        .is_none_or(|at| SystemTime::now() >= at);
+                    state.stale.store(expired, Ordering::SeqCst);
+
+                    #[cfg(feature = "logging")]
+                    warn!(error = %_e, stale = expired, "Secret refresh failed");
+
+                    tokio::time::sleep(jitter(backoff)).await;
+                    backoff = (backoff * 2).min(refresher.max_backoff);
+                }
+            }
+        }
+    }
+}
+
+/// How long to wait before refreshing a value that expires at `expires_at`.
+///
+/// Normally `refresh_ahead` before expiry. When the TTL is shorter than that,
+/// refresh halfway through the remaining lifetime instead, and never sooner
+/// than `min_refresh_delay`.
+fn next_refresh_in<P>(refresher: &SecretRefresher<P>, expires_at: Option<SystemTime>) -> Duration {
+    let Some(at) = expires_at else {
+        return refresher.refresh_interval;
+    };
+    let remaining = at.duration_since(SystemTime::now()).unwrap_or_default();
+    let wait = match remaining.checked_sub(refresher.refresh_ahead) {
+        Some(wait) if !wait.is_zero() => wait,
+        _ => remaining / 2,
+    };
+    wait.max(refresher.min_refresh_delay)
+}
+
+/// A random delay in `[backoff / 2, backoff]`, so retries from many
+/// processes do not line up.
+fn jitter(backoff: Duration) -> Duration {
+    let mut bytes = [0u8; 8];
+    if getrandom::getrandom(&mut bytes).is_err() {
+        return backoff;
+    }
+    let fraction = u64::from_le_bytes(bytes) as f64 / u64::MAX as f64;
+    backoff.mul_f64(0.5 + fraction / 2.0)
+}
+
+/// Strips a single trailing `\n` or `\r\n`, as left by `echo` or editors.
+fn trim_newline(bytes: &mut Vec<u8>) {
+    if bytes.last() == Some(&b'\n') {
+        bytes.pop();
+        if bytes.last() == Some(&b'\r') {
+            bytes.pop();
+        }
+    }
+}
+
+/// Reads a secret from an environment variable.
+pub struct EnvProvider {
+    var: String,
+}
+
+impl EnvProvider {
+    pub fn new(var: impl Into<String>) -> Self {
+        Self { var: var.into() }
+    }
+}
+
+#[async_trait]
+impl SecretProvider<Vec<u8>> for EnvProvider {
+    async fn fetch(&self) -> Result<FetchedSecret<Vec<u8>>, ProviderError> {
+        let value = std::env::var_os(&self.var)
+            .ok_or_else(|| ProviderError::NotFound(self.var.clone()))?;
+        Ok(FetchedSecret::new(value.into_encoded_bytes()))
+    }
+}
+
+/// Reads a secret from a file, such as a mounted Kubernetes or Docker secret.
+///
+/// A single trailing newline is stripped.
+pub struct FileProvider {
+    path: PathBuf,
+}
+
+impl FileProvider {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl SecretProvider<Vec<u8>> for FileProvider {
+    async fn fetch(&self) -> Result<FetchedSecret<Vec<u8>>, ProviderError> {
+        let mut bytes = tokio::fs::read(&self.path).await.map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => ProviderError::NotFound(self.path.display().to_string()),
+            _ => ProviderError::Io(e),
+        })?;
+        trim_newline(&mut bytes);
+        Ok(FetchedSecret::new(bytes))
+    }
+}
+
+/// Runs a command and uses its standard output as the secret.
+///
+/// A single trailing newline is stripped. Standard error is discarded and
+/// wiped, and a non-zero exit status is reported as
+/// [`ProviderError::CommandFailed`].
+pub struct CommandProvider {
+    program: OsString,
+    args: Vec<OsString>,
+}
+
+impl CommandProvider {
+    pub fn new(program: impl Into<OsString>) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+        }
+    }
+
+    pub fn arg(mut self, arg: impl Into<OsString>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+}
+
+#[async_trait]
+impl SecretProvider<Vec<u8>> for CommandProvider {
+    async fn fetch(&self) -> Result<FetchedSecret<Vec<u8>>, ProviderError> {
+        let mut output = tokio::process::Command::new(&self.program)
+            .args(&self.args)
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true)
+            .output()
+            .await?;
+        output.stderr.zeroize();
+
+        if !output.status.success() {
+            output.stdout.zeroize();
+            return Err(ProviderError::CommandFailed(output.status.code()));
+        }
+
+        trim_newline(&mut output.stdout);
+        Ok(FetchedSecret::new(output.stdout))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use std::sync::atomic::AtomicU8;
+
+    /// Returns `[n]` on the n-th fetch, and fails while `failing` is set.
+    #[derive(Clone, Default)]
+    struct CountingProvider {
+        calls: Arc<AtomicU8>,
+        failing: Arc<AtomicBool>,
+        ttl: Option<Duration>,
+    }
+
+    #[async_trait]
+    impl SecretProvider<Vec<u8>> for CountingProvider {
+        async fn fetch(&self) -> Result<FetchedSecret<Vec<u8>>, ProviderError> {
+            if self.failing.load(Ordering::SeqCst) {
+                return Err(ProviderError::Other("unavailable".into()));
+            }
+            let n = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            let fetched = FetchedSecret::new(vec![n]);
+            Ok(match self.ttl {
+                Some(ttl) => fetched.expires_at(SystemTime::now() + ttl),
+                None => fetched,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn refreshes_on_interval() {
+        let secret = SecretRefresher::new(CountingProvider::default())
+            .refresh_interval(Duration::from_millis(10))
+            .start()
+            .await
+            .unwrap();
+        assert_eq!(*secret.value().expose().await, vec![1]);
+
+        let mut changes = secret.value().subscribe();
+        changes.next().await;
+        assert!(secret.value().with(|v| v[0]).await >= 2);
+        assert!(!secret.is_stale());
+    }
+
+    #[tokio::test]
+    async fn refreshes_ahead_of_expiry() {
+        let provider = CountingProvider {
+            ttl: Some(Duration::from_millis(200)),
+            ..Default::default()
+        };
+        let secret = SecretRefresher::new(provider)
+            .refresh_ahead(Duration::from_millis(190))
+            .min_refresh_delay(Duration::from_millis(1))
+            .start()
+            .await
+            .unwrap();
+
+        let mut changes = secret.value().subscribe();
+        tokio::time::timeout(Duration::from_millis(150), changes.next())
+            .await
+            .expect("refresh did not happen ahead of expiry");
+        assert!(secret.expires_at().is_some());
+    }
+
+    #[tokio::test]
+    async fn short_or_expired_ttls_do_not_spin() {
+        for ttl in [Duration::from_millis(50), Duration::ZERO] {
+            let provider = CountingProvider {
+                ttl: Some(ttl),
+                ..Default::default()
+            };
+            let calls = Arc::clone(&provider.calls);
+            let _secret = SecretRefresher::new(provider)
+                .refresh_ahead(Duration::from_secs(30))
+                .min_refresh_delay(Duration::from_millis(20))
+                .start()
+                .await
+                .unwrap();
+
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            let fetched = calls.load(Ordering::SeqCst);
+            assert!((2..=15).contains(&fetched), "{ttl:?}: {fetched} fetches");
+        }
+    }
+
+    #[test]
+    fn refresh_waits_are_clamped() {
+        let refresher = SecretRefresher::new(())
+            .refresh_ahead(Duration::from_secs(30))
+            .min_refresh_delay(Duration::from_secs(1));
+        let now = SystemTime::now();
+
+        let long = next_refresh_in(&refresher, Some(now + Duration::from_secs(600)));
+        assert!(long > Duration::from_secs(560) && long <= Duration::from_secs(570));
+        let short = next_refresh_in(&refresher, Some(now + Duration::from_secs(10)));
+        assert!(short > Duration::from_secs(4) && short <= Duration::from_secs(5));
+        assert_eq!(next_refresh_in(&refresher, Some(now)), Duration::from_secs(1));
+        assert_eq!(next_refresh_in(&refresher, None), Duration::from_secs(300));
+    }
+
+    #[tokio::test]
+    async fn serves_last_good_value_while_failing() {
+        let provider = CountingProvider::default();
+        let failing = Arc::clone(&provider.failing);
+        let secret = SecretRefresher::new(provider)
+            .refresh_interval(Duration::from_millis(5))
+            .backoff(Duration::from_millis(2), Duration::from_millis(4))
+            .start()
+            .await
+            .unwrap();
+
+        failing.store(true, Ordering::SeqCst);
+        let last = secret.value().with(|v| v[0]).await;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert!(secret.is_stale());
+        assert!(secret.value().with(|v| v[0]).await <= last + 1);
+
+        failing.store(false, Ordering::SeqCst);
+        let mut changes = secret.value().subscribe();
+        changes.next().await;
+        assert!(!secret.is_stale());
+    }
+
+    #[tokio::test]
+    async fn initial_fetch_failure_is_reported() {
+        let provider = CountingProvider::default();
+        provider.failing.store(true, Ordering::SeqCst);
+        assert!(SecretRefresher::new(provider).start::<Vec<u8>>().await.is_err());
+    }
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        let backoff = Duration::from_millis(100);
+        for _ in 0..32 {
+            let delay = jitter(backoff);
+            assert!(delay >= backoff / 2 && delay <= backoff);
+        }
+    }
+
+    #[tokio::test]
+    async fn env_provider_reads_variable() {
+        assert!(matches!(
+            EnvProvider::new("CLASSIFIED_TEST_UNSET_VAR").fetch().await,
+            Err(ProviderError::NotFound(_))
+        ));
+
+        let path = std::env::var("PATH").unwrap_or_default();
+        let fetched = EnvProvider::new("PATH").fetch().await.unwrap();
+        assert_eq!(fetched.value.expose(), path.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn file_provider_strips_trailing_newline() {
+        let path = std::env::temp_dir().join(format!("classified-secret-{}", std::process::id()));
+        tokio::fs::write(&path, b"s3cret\n").await.unwrap();
+
+        let fetched = FileProvider::new(&path).fetch().await.unwrap();
+        tokio::fs::remove_file(&path).await.unwrap();
+        assert_eq!(fetched.value.expose(), b"s3cret");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_provider_uses_stdout() {
+        let fetched = CommandProvider::new("echo").arg("token").fetch().await.unwrap();
+        assert_eq!(fetched.value.expose(), b"token");
+
+        assert!(matches!(
+            CommandProvider::new("false").fetch().await,
+            Err(ProviderError::CommandFailed(Some(1)))
+        ));
+    }
+}
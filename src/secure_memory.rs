@@ -0,0 +1,448 @@
+//! ----------------------------------------------
+//! DOCUMENT DETAILS -----------------------------
+//!
+//! filename:secure_memory.rs
+//! description:page-locked and guard-page backing storage for secrets
+//! usages:[locked_classified_data.rs]
+//!
+//! ----------------------------------------------
+//! FEATURE NOTES --------------------------------
+//!
+//! feature_name:std
+//! deps:[std][libc][getrandom]
+//! scope:[struct PageBox][type LockedBox][type GuardedBox]
+//! corpus:false
+//!
+//! ----------------------------------------------
+//! CORPUS FEATURES ------------------------------
+//!
+#![cfg(feature = "std")]
+#![cfg(unix)]
+
+//! Page-locked storage for secret values.
+//!
+//! [`LockedBox<T>`] places a single value on its own anonymous mapping that is
+//! pinned with `mlock` so it is never written to swap. On Linux the mapping is
+//! additionally marked `MADV_DONTDUMP` (excluded from core dumps) and
+//! `MADV_WIPEONFORK` (reads as zeroes in forked children).
+//!
+//! [`GuardedBox<T>`] uses the same locked pages but follows the layout of
+//! libsodium's `sodium_malloc`: the value is pushed against a trailing
+//! `PROT_NONE` guard page, a leading guard page sits in front of the data, and
+//! a random canary is written directly before the value. Running off either
+//! end of the secret faults, and a clobbered canary aborts the process on drop.
+//!
+//! Only the bytes of `T` itself live on the locked pages. Heap buffers owned by
+//! `T` (the contents of a `Vec<u8>` or `String`) are allocated normally, so
+//! fixed-size types such as `[u8; 32]` get the full benefit.
+
+use std::{
+    io,
+    mem::{self, MaybeUninit},
+    ptr::{self, NonNull},
+    slice,
+    sync::OnceLock,
+};
+
+use secrecy::{ExposeSecret, ExposeSecretMut};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+/// Size of the canary written in front of guarded values.
+pub const CANARY_LEN: usize = 16;
+
+/// Returns the size of a virtual memory page.
+fn page_size() -> usize {
+    // SAFETY: `sysconf` has no memory-safety preconditions.
+    let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    if size <= 0 { 4096 } else { size as usize }
+}
+
+fn round_up(len: usize, page: usize) -> usize {
+    len.div_ceil(page) * page
+}
+
+/// Process-wide random canary, drawn once from the OS.
+fn canary() -> &'static [u8; CANARY_LEN] {
+    static CANARY: OnceLock<[u8; CANARY_LEN]> = OnceLock::new();
+    CANARY.get_or_init(|| {
+        let mut bytes = [0u8; CANARY_LEN];
+        getrandom::getrandom(&mut bytes).expect("failed to draw canary from the OS");
+        bytes
+    })
+}
+
+/// Pinned storage for one value; `GUARDED` selects the guard page layout.
+///
+/// Use it through the [`LockedBox`] and [`GuardedBox`] aliases.
+pub struct PageBox<T: Zeroize, const GUARDED: bool> {
+    ptr: NonNull<T>,
+    /// Start and length of the whole mapping, guard pages included.
+    mapping: NonNull<u8>,
+    mapping_len: usize,
+    /// Start and length of the locked, readable part of the mapping.
+    data: NonNull<u8>,
+    data_len: usize,
+}
+
+/// A heap-like box whose pages are pinned in RAM and wiped before release.
+///
+/// The value is zeroized, dropped, and the whole mapping is overwritten with
+/// zeroes before it is unlocked and unmapped.
+pub type LockedBox<T> = PageBox<T, false>;
+
+/// A [`LockedBox`] surrounded by `PROT_NONE` guard pages and a canary.
+///
+/// # Aborts
+/// Dropping a `GuardedBox` whose canary was overwritten aborts the process,
+/// since the heap around the secret can no longer be trusted.
+pub type GuardedBox<T> = PageBox<T, true>;
+
+impl<T: Zeroize, const GUARDED: bool> PageBox<T, GUARDED> {
+    /// Moves `value` onto freshly mapped, locked pages.
+    ///
+    /// # Panics
+    /// Panics if the pages cannot be mapped or locked (for example when
+    /// `RLIMIT_MEMLOCK` is exhausted). Use [`PageBox::try_new`] to handle
+    /// that case.
+    pub fn new(value: T) -> Self {
+        Self::try_new(value).expect("failed to allocate locked memory")
+    }
+
+    /// Moves `value` onto freshly mapped, locked pages.
+    ///
+    /// On failure the value is zeroized before the error is returned.
+    pub fn try_new(mut value: T) -> io::Result<Self> {
+        let page = page_size();
+        if mem::align_of::<T>() > page {
+            value.zeroize();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "alignment exceeds page size",
+            ));
+        }
+
+        let size = mem::size_of::<T>();
+        let (mapping_len, data_offset, data_len) = if GUARDED {
+            let data_len = round_up(CANARY_LEN + size + mem::align_of::<T>(), page);
+            (data_len + 2 * page, page, data_len)
+        } else {
+            let data_len = round_up(size.max(1), page);
+            (data_len, 0, data_len)
+        };
+
+        let mapping = match map_locked(mapping_len, data_offset, data_len) {
+            Ok(mapping) => mapping,
+            Err(e) => {
+                value.zeroize();
+                return Err(e);
+            }
+        };
+
+        // SAFETY: `data_offset + data_len <= mapping_len`.
+        let data = unsafe { mapping.add(data_offset) };
+        let ptr = if GUARDED {
+            // Push the value against the trailing guard page, keeping alignment.
+            let end = data.as_ptr() as usize + data_len;
+            let addr = (end - size) & !(mem::align_of::<T>() - 1);
+            let ptr = NonNull::new(addr as *mut T).expect("guarded pointer is null");
+            // SAFETY: the canary slot lies between `data` and `ptr`.
+            unsafe {
+                ptr::copy_nonoverlapping(
+                    canary().as_ptr(),
+                    ptr.cast::<u8>().as_ptr().sub(CANARY_LEN),
+                    CANARY_LEN,
+                );
+            }
+            ptr
+        } else {
+            data.cast::<T>()
+        };
+
+        // SAFETY: `ptr` is aligned, lies inside the readable part of the mapping
+        // with room for a `T`, and the mapping is exclusively owned by this box.
+        unsafe { ptr::write(ptr.as_ptr(), value) };
+
+        Ok(Self { ptr, mapping, mapping_len, data, data_len })
+    }
+
+    /// Number of bytes of locked memory held by this box.
+    pub fn locked_len(&self) -> usize {
+        self.data_len
+    }
+
+    /// Checks the canary in front of a guarded value.
+    ///
+    /// Always `true` for unguarded boxes.
+    pub fn canary_intact(&self) -> bool {
+        if !GUARDED {
+            return true;
+        }
+        // SAFETY: guarded boxes keep `CANARY_LEN` readable bytes before `ptr`.
+        let stored = unsafe {
+            slice::from_raw_parts(self.ptr.cast::<u8>().as_ptr().sub(CANARY_LEN), CANARY_LEN)
+        };
+        stored.ct_eq(canary()).into()
+    }
+}
+
+/// Maps `mapping_len` bytes of anonymous memory, locks the
+/// `data_offset..data_offset + data_len` window and turns everything outside
+/// it into `PROT_NONE` guard pages.
+fn map_locked(mapping_len: usize, data_offset: usize, data_len: usize) -> io::Result<NonNull<u8>> {
+    // SAFETY: anonymous private mapping, no file descriptor involved.
+    let addr = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            mapping_len,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    if addr == libc::MAP_FAILED {
+        return Err(io::Error::last_os_error());
+    }
+
+    // SAFETY: every range passed below lies inside the mapping created above.
+    let result = unsafe { protect_mapping(addr.cast::<u8>(), mapping_len, data_offset, data_len) };
+    if let Err(e) = result {
+        // SAFETY: unmapping the region we just created.
+        unsafe { libc::munmap(addr, mapping_len) };
+        return Err(e);
+    }
+
+    Ok(NonNull::new(addr.cast::<u8>()).expect("mmap returned null"))
+}
+
+unsafe fn protect_mapping(
+    base: *mut u8,
+    mapping_len: usize,
+    data_offset: usize,
+    data_len: usize,
+) -> io::Result<()> {
+    let data = unsafe { base.add(data_offset) }.cast::<libc::c_void>();
+
+    if data_offset > 0 {
+        let front = base.cast::<libc::c_void>();
+        let back = unsafe { base.add(data_offset + data_len) }.cast::<libc::c_void>();
+        let back_len = mapping_len - data_offset - data_len;
+        if unsafe { libc::mprotect(front, data_offset, libc::PROT_NONE) } != 0
+            || unsafe { libc::mprotect(back, back_len, libc::PROT_NONE) } != 0
+        {
+            return Err(io::Error::last_os_error());
+        }
+    }
+
+    if unsafe { libc::mlock(data, data_len) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let whole = base.cast::<libc::c_void>();
+        if unsafe { libc::madvise(whole, mapping_len, libc::MADV_DONTDUMP) } != 0 {
+            let e = io::Error::last_os_error();
+            unsafe { libc::munlock(data, data_len) };
+            return Err(e);
+        }
+        // Best effort: MADV_WIPEONFORK only exists on Linux 4.14+.
+        unsafe { libc::madvise(whole, mapping_len, libc::MADV_WIPEONFORK) };
+    }
+
+    Ok(())
+}
+
+impl<T: Zeroize, const GUARDED: bool> ExposeSecret<T> for PageBox<T, GUARDED> {
+    fn expose_secret(&self) -> &T {
+        // SAFETY: `ptr` points at an initialized `T` for the lifetime of `self`.
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T: Zeroize, const GUARDED: bool> ExposeSecretMut<T> for PageBox<T, GUARDED> {
+    fn expose_secret_mut(&mut self) -> &mut T {
+        // SAFETY: as above, and `&mut self` guarantees exclusive access.
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T: Zeroize, const GUARDED: bool> Zeroize for PageBox<T, GUARDED> {
+    fn zeroize(&mut self) {
+        self.expose_secret_mut().zeroize();
+    }
+}
+
+/// Moves a boxed value onto locked pages, wiping the heap slot it came from.
+impl<T: Zeroize, const GUARDED: bool> From<Box<T>> for PageBox<T, GUARDED> {
+    fn from(boxed: Box<T>) -> Self {
+        let raw = Box::into_raw(boxed);
+        // SAFETY: `raw` came from `Box::into_raw` and is read exactly once.
+        let value = unsafe { ptr::read(raw) };
+        // SAFETY: the slot is now logically uninitialized; reinterpreting it as
+        // `MaybeUninit<T>` frees it with the original layout and without a drop.
+        let mut slot = unsafe { Box::from_raw(raw.cast::<MaybeUninit<T>>()) };
+        unsafe { slice::from_raw_parts_mut(slot.as_mut_ptr().cast::<u8>(), mem::size_of::<T>()) }
+            .zeroize();
+        drop(slot);
+
+        Self::new(value)
+    }
+}
+
+impl<T: Zeroize, const GUARDED: bool> Drop for PageBox<T, GUARDED> {
+    fn drop(&mut self) {
+        if !self.canary_intact() {
+            // The secret's neighbourhood was overwritten; do not run any more
+            // code that touches it.
+            std::process::abort();
+        }
+
+        // SAFETY: `ptr` holds an initialized `T` that is dropped exactly once,
+        // after which the data pages are only touched as raw bytes.
+        unsafe {
+            self.ptr.as_mut().zeroize();
+            ptr::drop_in_place(self.ptr.as_ptr());
+
+            slice::from_raw_parts_mut(self.data.as_ptr(), self.data_len).zeroize();
+
+            libc::munlock(self.data.as_ptr().cast::<libc::c_void>(), self.data_len);
+            libc::munmap(self.mapping.as_ptr().cast::<libc::c_void>(), self.mapping_len);
+        }
+    }
+}
+
+// SAFETY: a `PageBox<T, _>` uniquely owns its `T`, exactly like `Box<T>`.
+unsafe impl<T: Zeroize + Send, const GUARDED: bool> Send for PageBox<T, GUARDED> {}
+unsafe impl<T: Zeroize + Sync, const GUARDED: bool> Sync for PageBox<T, GUARDED> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stores_and_exposes_value() {
+        let locked = LockedBox::new([7u8; 32]);
+        assert_eq!(locked.expose_secret(), &[7u8; 32]);
+    }
+
+    #[test]
+    fn region_is_page_aligned_and_rounded() {
+        let locked = LockedBox::new([1u8; 5000]);
+        let page = page_size();
+        assert_eq!(locked.locked_len() % page, 0);
+        assert!(locked.locked_len() >= 5000);
+        assert_eq!(locked.expose_secret() as *const _ as usize % page, 0);
+    }
+
+    #[test]
+    fn mutation_through_expose_secret_mut() {
+        let mut locked = LockedBox::new(vec![1u8, 2, 3]);
+        locked.expose_secret_mut().push(4);
+        assert_eq!(locked.expose_secret(), &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn zeroize_clears_value() {
+        let mut locked = LockedBox::new([9u8; 16]);
+        locked.zeroize();
+        assert_eq!(locked.expose_secret(), &[0u8; 16]);
+    }
+
+    #[test]
+    fn from_box_moves_value() {
+        let locked = LockedBox::from(Box::new(0xDEADBEEFu64));
+        assert_eq!(*locked.expose_secret(), 0xDEADBEEF);
+    }
+
+    #[test]
+    fn drop_runs_inner_destructor() {
+        use std::rc::Rc;
+
+        #[derive(Zeroize)]
+        struct Tracked(#[zeroize(skip)] Rc<()>, u8);
+
+        let rc = Rc::new(());
+        {
+            let _locked = LockedBox::new(Tracked(rc.clone(), 1));
+            assert_eq!(Rc::strong_count(&rc), 2);
+        }
+        assert_eq!(Rc::strong_count(&rc), 1);
+    }
+
+    #[test]
+    fn guarded_value_touches_trailing_guard_page() {
+        let guarded = GuardedBox::new([3u8; 100]);
+        let end = guarded.expose_secret().as_ptr() as usize + 100;
+        assert_eq!(end % page_size(), 0);
+        assert!(guarded.canary_intact());
+        assert_eq!(guarded.expose_secret(), &[3u8; 100]);
+    }
+
+    #[test]
+    fn guarded_respects_alignment() {
+        let guarded = GuardedBox::new([0xAAu64; 3]);
+        let addr = guarded.expose_secret() as *const _ as usize;
+        assert_eq!(addr % mem::align_of::<u64>(), 0);
+        assert_eq!(guarded.expose_secret(), &[0xAAu64; 3]);
+    }
+
+    #[test]
+    fn canary_mismatch_is_detected() {
+        let guarded = GuardedBox::new([1u8; 8]);
+        // SAFETY: test-only write into the canary slot, restored before drop.
+        let slot = unsafe { guarded.ptr.cast::<u8>().as_ptr().sub(1) };
+        let original = unsafe { *slot };
+        unsafe { *slot = !original };
+        assert!(!guarded.canary_intact());
+        unsafe { *slot = original };
+        assert!(guarded.canary_intact());
+    }
+
+    const CHILD_ENV: &str = "CLASSIFIED_SECURE_MEMORY_CHILD";
+
+    fn in_child() -> bool {
+        std::env::var_os(CHILD_ENV).is_some()
+    }
+
+    /// Re-runs the test `name` from this module in a fresh process and returns
+    /// the signal that killed it. Forking the multithreaded test harness
+    /// instead is not async-signal-safe and can deadlock the child.
+    fn child_signal(name: &str) -> Option<i32> {
+        use std::os::unix::process::ExitStatusExt;
+
+        let module = module_path!().split_once("::").map_or(module_path!(), |(_, m)| m);
+        std::process::Command::new(std::env::current_exe().expect("test binary path"))
+            .args([&format!("{module}::{name}"), "--exact", "--test-threads=1"])
+            .env(CHILD_ENV, "1")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .expect("failed to re-run test binary")
+            .signal()
+    }
+
+    #[test]
+    fn overflow_past_guarded_value_faults() {
+        if in_child() {
+            let guarded = GuardedBox::new([0u8; 32]);
+            let past_end = guarded.expose_secret().as_ptr() as usize + 32;
+            unsafe { ptr::write_volatile(past_end as *mut u8, 1) };
+            return;
+        }
+        let signal = child_signal("overflow_past_guarded_value_faults");
+        assert!(matches!(signal, Some(libc::SIGSEGV) | Some(libc::SIGBUS)));
+    }
+
+    #[test]
+    fn clobbered_canary_aborts_on_drop() {
+        if in_child() {
+            let guarded = GuardedBox::new([0u8; 32]);
+            unsafe { *guarded.ptr.cast::<u8>().as_ptr().sub(CANARY_LEN) ^= 0xFF };
+            drop(guarded);
+            return;
+        }
+        assert_eq!(child_signal("clobbered_canary_aborts_on_drop"), Some(libc::SIGABRT));
+    }
+}
@@ -23,6 +23,7 @@
 //! 
 #![cfg(feature = "async")]
 
+use crate::crypto::crypto_algorithm::CryptoAlgorithm;
 use crate::errors::SecureError;
 use crate::key_manager::KeyManager;
 use crate::pipelines::Pipeline;
@@ -47,8 +48,8 @@ impl SecureService {
         self.pipeline.run(sensitive_data).await
     }
 
-    pub async fn add_key(&self, id: String, key: Vec<u8>) -> Result<(), SecureError> {
-        self.key_manager.accept_key(&id, key.len(), key).await
+    pub async fn add_key(&self, id: String, algorithm: &CryptoAlgorithm, key: Vec<u8>) -> Result<(), SecureError> {
+        self.key_manager.accept_key(&id, algorithm, key).await
     }
 
     pub async fn get_key(&self, id: &str) -> Option<ClassifiedData<Vec<u8>>> {
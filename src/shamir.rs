@@ -0,0 +1,409 @@
+//! ----------------------------------------------
+//! DOCUMENT DETAILS -----------------------------
+//!
+//! filename:shamir.rs
+//! description:Shamir secret sharing of classified bytes over GF(256)
+//! usages:none in crate yet
+//!
+//! ----------------------------------------------
+//! FEATURE NOTES --------------------------------
+//!
+//! feature_name:async
+//! deps:[tokio][async_trait]
+//! scope:[fn split][fn combine]
+//! corpus:true
+//!
+//! feature_name:std
+//! deps:[std][sha2][getrandom]
+//! scope:[struct Share]
+//! corpus:false
+//!
+//! ----------------------------------------------
+//! CORPUS FEATURES ------------------------------
+//!
+#![cfg(feature = "async")]
+#![cfg(feature = "std")]
+
+//! Shamir secret sharing for master keys.
+//!
+//! [`split`] turns a secret into `n` [`Share`]s such that any `threshold` of
+//! them recover it with [`combine`] and fewer reveal nothing. Each byte of
+//! the secret is the constant term of its own random polynomial over
+//! [`Gf256`]; share `i` holds the polynomials evaluated at `x = i`.
+//!
+//! Shares are meant to leave the process (printed for a safe, handed to
+//! break-glass custodians), so they carry a format version, a random id
+//! shared by every share of one split, and a checksum, and serialize to
+//! base64 through the constant-time encoder.
+
+use std::fmt;
+
+use secrecy::{ExposeSecret, SecretBox};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+use crate::{
+    classified_data::ClassifiedData,
+    classified_string::ClassifiedString,
+    constant_time::{base64_decode, base64_encode},
+    errors::ShamirError,
+    gf::{Field, Gf256},
+    scratch::Scratch,
+};
+
+/// Current share format version.
+pub const SHARE_VERSION: u8 = 1;
+
+/// Bytes of the truncated SHA-256 checksum at the end of an encoded share.
+const CHECKSUM_LEN: usize = 4;
+
+/// Bytes of the random id drawn once per [`split`].
+const SPLIT_ID_LEN: usize = 8;
+
+/// Version, threshold, index and split id precede the share data.
+const HEADER_LEN: usize = 3 + SPLIT_ID_LEN;
+
+/// One share of a split secret, zeroized on drop.
+///
+/// The checksum only catches corruption of a single share. Shares from
+/// different splits are told apart by their split id, which [`combine`]
+/// requires to match. Neither is a MAC: nothing stops a custodian from
+/// forging a share.
+pub struct Share {
+    version: u8,
+    threshold: u8,
+    index: u8,
+    split_id: [u8; SPLIT_ID_LEN],
+    data: SecretBox<Vec<u8>>,
+}
+
+impl Share {
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Number of shares needed to recover the secret.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// The share's x coordinate, from 1 to 255.
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Encode as base64: version, threshold, index, split id, data and checksum.
+    pub fn to_base64(&self) -> ClassifiedString {
+        let mut bytes = self.to_bytes();
+        let encoded = ClassifiedString::new(base64_encode(&bytes));
+        bytes.zeroize();
+        encoded
+    }
+
+    /// Decode a share produced by [`to_base64`](Self::to_base64).
+    pub fn from_base64(encoded: &str) -> Result<Self, ShamirError> {
+        let mut bytes = base64_decode(encoded)?;
+        let share = Self::from_bytes(&bytes);
+        bytes.zeroize();
+        share
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let data = self.data.expose_secret();
+        let mut bytes = Vec::with_capacity(HEADER_LEN + data.len() + CHECKSUM_LEN);
+        bytes.extend_from_slice(&[self.version, self.threshold, self.index]);
+        bytes.extend_from_slice(&self.split_id);
+        bytes.extend_from_slice(data);
+        let checksum = checksum(&bytes);
+        bytes.extend_from_slice(&checksum);
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, ShamirError> {
+        if bytes.len() < HEADER_LEN + CHECKSUM_LEN {
+            return Err(ShamirError::Malformed);
+        }
+        let (body, stored) = bytes.split_at(bytes.len() - CHECKSUM_LEN);
+        let (version, threshold, index) = (body[0], body[1], body[2]);
+        if version != SHARE_VERSION {
+            return Err(ShamirError::UnsupportedVersion(version));
+        }
+        if checksum(body) != stored {
+            return Err(ShamirError::ChecksumMismatch(index));
+        }
+        if index == 0 || threshold < 2 {
+            return Err(ShamirError::Malformed);
+        }
+        let mut split_id = [0u8; SPLIT_ID_LEN];
+        split_id.copy_from_slice(&body[3..HEADER_LEN]);
+        Ok(Self {
+            version,
+            threshold,
+            index,
+            split_id,
+            data: SecretBox::new(Box::new(body[HEADER_LEN..].to_vec())),
+        })
+    }
+}
+
+fn checksum(body: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let digest = Sha256::digest(body);
+    let mut out = [0u8; CHECKSUM_LEN];
+    out.copy_from_slice(&digest[..CHECKSUM_LEN]);
+    out
+}
+
+/// Serializes as the base64 string of [`Share::to_base64`].
+impl Serialize for Share {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.to_base64().expose())
+    }
+}
+
+impl<'de> Deserialize<'de> for Share {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let encoded = ClassifiedString::deserialize(deserializer)?;
+        Share::from_base64(encoded.expose()).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Drop for Share {
+    fn drop(&mut self) {
+        self.data.zeroize();
+    }
+}
+
+impl fmt::Debug for Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Share({}/{}, <REDACTED>)", self.index, self.threshold)
+    }
+}
+
+/// Split `secret` into `shares` shares, any `threshold` of which recover it.
+///
+/// Requires `2 <= threshold <= shares`; at most 255 shares fit in GF(256).
+/// Fails with [`ShamirError::Rng`] if the OS random number generator does.
+///
+/// # Example
+/// ```
+/// use classified::classified_data::ClassifiedData;
+/// use classified::shamir::{combine, split};
+///
+/// let master = ClassifiedData::new(b"master key".to_vec());
+/// let shares = split(&master, 3, 5).unwrap();
+///
+/// let recovered = combine(&shares[1..4]).unwrap();
+/// assert_eq!(recovered.expose(), master.expose());
+/// assert!(combine(&shares[..2]).is_err());
+/// ```
+pub fn split(secret: &ClassifiedData<Vec<u8>>, threshold: u8, shares: u8) -> Result<Vec<Share>, ShamirError> {
+    if threshold < 2 || threshold > shares {
+        return Err(ShamirError::InvalidThreshold { threshold, shares });
+    }
+
+    let secret = secret.expose();
+    let scratch = Scratch::new();
+    // Coefficients 1..threshold of every byte's polynomial, byte-major.
+    let degree = usize::from(threshold - 1);
+    let coefficients = scratch.bytes(secret.len() * degree);
+    getrandom::getrandom(coefficients).map_err(ShamirError::Rng)?;
+    let mut split_id = [0u8; SPLIT_ID_LEN];
+    getrandom::getrandom(&mut split_id).map_err(ShamirError::Rng)?;
+
+    let shares = (1..=shares)
+        .map(|index| {
+            let x = Gf256::new(index);
+            let data = secret
+                .iter()
+                .zip(coefficients.chunks_exact(degree))
+                .map(|(&byte, coeffs)| {
+                    // Horner's rule from the highest coefficient down.
+                    let y = coeffs
+                        .iter()
+                        .rev()
+                        .fold(Gf256::ZERO, |acc, &c| acc * x + Gf256::new(c));
+                    (y * x + Gf256::new(byte)).value()
+                })
+                .collect();
+            Share {
+                version: SHARE_VERSION,
+                threshold,
+                index,
+                split_id,
+                data: SecretBox::new(Box::new(data)),
+            }
+        })
+        .collect();
+    Ok(shares)
+}
+
+/// Recover the secret from at least `threshold` shares of it.
+///
+/// All shares must come from the same [`split`]: same version, split id,
+/// threshold and length, with distinct indices.
+pub fn combine(shares: &[Share]) -> Result<ClassifiedData<Vec<u8>>, ShamirError> {
+    let first = shares.first().ok_or(ShamirError::NotEnoughShares { needed: 2, got: 0 })?;
+    let threshold = first.threshold;
+    let len = first.data.expose_secret().len();
+
+    for (i, share) in shares.iter().enumerate() {
+        if share.version != SHARE_VERSION {
+            return Err(ShamirError::UnsupportedVersion(share.version));
+        }
+        if share.split_id != first.split_id
+            || share.threshold != threshold
+            || share.data.expose_secret().len() != len
+        {
+            return Err(ShamirError::Mismatched);
+        }
+        if shares[..i].iter().any(|other| other.index == share.index) {
+            return Err(ShamirError::DuplicateShare(share.index));
+        }
+    }
+    if shares.len() < usize::from(threshold) {
+        return Err(ShamirError::NotEnoughShares {
+            needed: threshold,
+            got: shares.len(),
+        });
+    }
+
+    // Lagrange basis polynomials evaluated at x = 0. Indices are public.
+    let basis: Vec<Gf256> = shares
+        .iter()
+        .map(|share| {
+            let xj = Gf256::new(share.index);
+            shares
+                .iter()
+                .filter(|other| other.index != share.index)
+                .fold(Gf256::ONE, |acc, other| {
+                    let xm = Gf256::new(other.index);
+                    acc * xm * (xm - xj).invert().unwrap()
+                })
+        })
+        .collect();
+
+    let secret = (0..len)
+        .map(|i| {
+            shares
+                .iter()
+                .zip(&basis)
+                .fold(Gf256::ZERO, |acc, (share, &l)| {
+                    acc + Gf256::new(share.data.expose_secret()[i]) * l
+                })
+                .value()
+        })
+        .collect();
+    Ok(ClassifiedData::new(secret))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn master() -> ClassifiedData<Vec<u8>> {
+        ClassifiedData::new((0..=255u8).collect())
+    }
+
+    /// An independent copy of `share`, made through its encoding.
+    fn copy(share: &Share) -> Share {
+        Share::from_base64(share.to_base64().expose()).unwrap()
+    }
+
+    #[test]
+    fn any_threshold_subset_recovers() {
+        let secret = master();
+        let shares = split(&secret, 3, 5).unwrap();
+
+        for subset in [[0, 1, 2], [0, 2, 4], [4, 3, 1]] {
+            let picked: Vec<Share> = subset
+                .iter()
+                .map(|&i| copy(&shares[i]))
+                .collect();
+            assert_eq!(combine(&picked).unwrap().expose(), secret.expose());
+        }
+        assert_eq!(combine(&shares).unwrap().expose(), secret.expose());
+    }
+
+    #[test]
+    fn shares_do_not_contain_the_secret() {
+        let secret = ClassifiedData::new(vec![0x42; 64]);
+        let shares = split(&secret, 2, 3).unwrap();
+        for share in &shares {
+            assert_ne!(share.data.expose_secret(), secret.expose());
+        }
+    }
+
+    #[test]
+    fn rejects_bad_parameters_and_share_sets() {
+        let secret = master();
+        assert!(matches!(split(&secret, 1, 3), Err(ShamirError::InvalidThreshold { .. })));
+        assert!(matches!(split(&secret, 4, 3), Err(ShamirError::InvalidThreshold { .. })));
+
+        let shares = split(&secret, 3, 4).unwrap();
+        assert!(matches!(
+            combine(&shares[..2]),
+            Err(ShamirError::NotEnoughShares { needed: 3, got: 2 })
+        ));
+
+        let dup = copy(&shares[0]);
+        let set = [dup, copy(&shares[0])];
+        assert_eq!(combine(&set).unwrap_err(), ShamirError::DuplicateShare(1));
+
+        let other = split(&ClassifiedData::new(vec![1, 2, 3]), 3, 4).unwrap();
+        let mixed = [copy(&shares[0]), copy(&shares[1]), copy(&other[2])];
+        assert_eq!(combine(&mixed).unwrap_err(), ShamirError::Mismatched);
+    }
+
+    #[test]
+    fn rejects_shares_from_another_split_of_equal_length() {
+        let secret = master();
+        let first = split(&secret, 2, 3).unwrap();
+        let second = split(&ClassifiedData::new(vec![7u8; 256]), 2, 3).unwrap();
+
+        let mixed = [
+            copy(&first[0]),
+            copy(&second[1]),
+        ];
+        assert_eq!(combine(&mixed).unwrap_err(), ShamirError::Mismatched);
+
+        let resplit = split(&secret, 2, 3).unwrap();
+        let mixed = [
+            copy(&first[0]),
+            copy(&resplit[1]),
+        ];
+        assert_eq!(combine(&mixed).unwrap_err(), ShamirError::Mismatched);
+    }
+
+    #[test]
+    fn corrupted_share_fails_checksum() {
+        let shares = split(&master(), 2, 2).unwrap();
+        let mut bytes = shares[1].to_bytes();
+        bytes[10] ^= 1;
+        assert_eq!(Share::from_bytes(&bytes).unwrap_err(), ShamirError::ChecksumMismatch(2));
+
+        bytes[0] = 9;
+        assert_eq!(Share::from_bytes(&bytes).unwrap_err(), ShamirError::UnsupportedVersion(9));
+    }
+
+    #[test]
+    fn serializes_through_serde_and_redacts_debug() {
+        #[derive(Serialize, Deserialize)]
+        struct Escrow {
+            share: Share,
+        }
+
+        let shares = split(&master(), 2, 2).unwrap();
+        let escrow = Escrow {
+            share: copy(&shares[0]),
+        };
+        let text = toml::to_string(&escrow).unwrap();
+        let back: Escrow = toml::from_str(&text).unwrap();
+
+        assert_eq!(back.share.index(), 1);
+        let second = copy(&shares[1]);
+        let recovered = combine(&[back.share, second]).unwrap();
+        assert_eq!(recovered.expose(), master().expose());
+        assert_eq!(format!("{:?}", shares[0]), "Share(1/2, <REDACTED>)");
+    }
+}
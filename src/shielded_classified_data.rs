@@ -0,0 +1,309 @@
+//! ----------------------------------------------
+//! DOCUMENT DETAILS -----------------------------
+//!
+//! filename:shielded_classified_data.rs
+//! description:secrets kept encrypted in memory while idle
+//! usages:[classified_map.rs][key_manager.rs]
+//!
+//! ----------------------------------------------
+//! FEATURE NOTES --------------------------------
+//!
+//! feature_name:async
+//! deps:[tokio][async_trait]
+//! scope:[fn ShieldedClassifiedData::unshield][impl ClassifiedStorage]
+//! corpus:true
+//!
+//! feature_name:std
+//! deps:[std][chacha20poly1305][sha2][getrandom]
+//! scope:[struct ShieldedClassifiedData]
+//! corpus:false
+//!
+//! ----------------------------------------------
+//! CORPUS FEATURES ------------------------------
+//!
+#![cfg(feature = "async")]
+#![cfg(feature = "std")]
+
+//! Shielded secrets, modelled on OpenSSH's shielded private keys.
+//!
+//! While idle the payload only exists as ciphertext. The encryption key is
+//! derived from a large random *prekey* (`SHA-512(prekey)`), so an attacker
+//! reading process memory through a side channel has to recover all of the
+//! prekey without bit errors before the payload becomes readable.
+//!
+//! The plaintext is reconstructed for the duration of an [`expose`] closure
+//! and wiped as soon as the closure returns or unwinds.
+//!
+//! [`expose`]: ShieldedClassifiedData::expose
+
+use std::{fmt, marker::PhantomData};
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use secrecy::{ExposeSecret, ExposeSecretMut, SecretBox};
+use sha2::{Digest, Sha512};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{classified_data::ClassifiedData, errors::CryptoError, traits::ClassifiedStorage};
+
+/// Size of the random prekey the shielding key is derived from.
+pub const PREKEY_LEN: usize = 16 * 1024;
+
+/// Types that can be turned into bytes for shielding and rebuilt afterwards.
+pub trait Shieldable: Zeroize + Sized {
+    /// Borrow the value as the bytes to encrypt.
+    fn shield_bytes(&self) -> &[u8];
+
+    /// Rebuild the value from decrypted bytes.
+    ///
+    /// Implementations own `bytes` and must not leave a copy behind.
+    fn from_shield_bytes(bytes: Vec<u8>) -> Self;
+}
+
+impl Shieldable for Vec<u8> {
+    fn shield_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn from_shield_bytes(bytes: Vec<u8>) -> Self {
+        bytes
+    }
+}
+
+impl<const N: usize> Shieldable for [u8; N] {
+    fn shield_bytes(&self) -> &[u8] {
+        self
+    }
+
+    fn from_shield_bytes(bytes: Vec<u8>) -> Self {
+        let bytes = Zeroizing::new(bytes);
+        let mut out = [0u8; N];
+        out.copy_from_slice(&bytes);
+        out
+    }
+}
+
+/// A classified value that stays encrypted in memory between uses.
+///
+/// Every time the payload is (re)shielded a fresh prekey is drawn, so each
+/// derived key encrypts exactly one message and a fixed nonce is safe.
+///
+/// # Example
+/// ```
+/// use classified::shielded_classified_data::ShieldedClassifiedData;
+/// let key = ShieldedClassifiedData::new(vec![1u8, 2, 3]);
+/// let sum: u32 = key.expose(|k| k.iter().map(|b| *b as u32).sum()).unwrap();
+/// assert_eq!(sum, 6);
+/// ```
+pub struct ShieldedClassifiedData<T: Shieldable> {
+    prekey: SecretBox<Vec<u8>>,
+    sealed: Vec<u8>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Shieldable> ShieldedClassifiedData<T> {
+    /// Shield `data`, wiping the plaintext argument afterwards.
+    ///
+    /// # Panics
+    /// Panics if the OS random number generator is unavailable.
+    pub fn new(data: T) -> Self {
+        let data = Zeroizing::new(data);
+        let (prekey, sealed) = shield(data.shield_bytes());
+        Self {
+            prekey,
+            sealed,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Temporarily decrypt the payload and pass it to `f`.
+    ///
+    /// The plaintext is zeroized before this method returns, including when
+    /// `f` panics. Fails with [`CryptoError::DecryptionError`] if the shielded
+    /// bytes were corrupted.
+    pub fn expose<R, F>(&self, f: F) -> Result<R, CryptoError>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        let plain = Zeroizing::new(self.unshield_value()?);
+        Ok(f(&plain))
+    }
+
+    /// Decrypt, mutate and reshield the payload under a fresh prekey.
+    pub fn update<F>(&mut self, f: F) -> Result<(), CryptoError>
+    where
+        F: FnOnce(&mut T),
+    {
+        let mut plain = Zeroizing::new(self.unshield_value()?);
+        f(&mut plain);
+
+        let (prekey, sealed) = shield(plain.shield_bytes());
+        self.prekey = prekey;
+        self.sealed.zeroize();
+        self.sealed = sealed;
+        Ok(())
+    }
+
+    /// Decrypt the payload into an ordinary [`ClassifiedData`].
+    pub fn unshield(&self) -> Result<ClassifiedData<T>, CryptoError> {
+        self.unshield_value().map(ClassifiedData::new)
+    }
+
+    fn unshield_value(&self) -> Result<T, CryptoError> {
+        let cipher = cipher_for(self.prekey.expose_secret());
+        let plain = cipher
+            .decrypt(&Nonce::default(), self.sealed.as_slice())
+            .map_err(|_| CryptoError::DecryptionError)?;
+        Ok(T::from_shield_bytes(plain))
+    }
+}
+
+/// Shields the payload straight from the wrapper's storage, without an
+/// intermediate plaintext copy; `data` is wiped when it drops.
+impl<T: Shieldable> From<ClassifiedData<T>> for ShieldedClassifiedData<T> {
+    fn from(data: ClassifiedData<T>) -> Self {
+        let (prekey, sealed) = shield(data.expose().shield_bytes());
+        Self {
+            prekey,
+            sealed,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Draws a new prekey and encrypts `plain` under the key derived from it.
+fn shield(plain: &[u8]) -> (SecretBox<Vec<u8>>, Vec<u8>) {
+    let mut prekey = SecretBox::new(Box::new(vec![0u8; PREKEY_LEN]));
+    getrandom::getrandom(prekey.expose_secret_mut())
+        .expect("failed to draw shielding prekey from the OS");
+
+    let sealed = cipher_for(prekey.expose_secret())
+        .encrypt(&Nonce::default(), plain)
+        .expect("ChaCha20-Poly1305 encryption cannot fail for in-memory buffers");
+    (prekey, sealed)
+}
+
+fn cipher_for(prekey: &[u8]) -> ChaCha20Poly1305 {
+    let mut digest = Sha512::digest(prekey);
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(&digest[..32]));
+    digest.as_mut_slice().zeroize();
+    cipher
+}
+
+impl<T: Shieldable> ClassifiedStorage<T> for ShieldedClassifiedData<T> {
+    fn classify(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: Shieldable> Drop for ShieldedClassifiedData<T> {
+    fn drop(&mut self) {
+        self.prekey.zeroize();
+        self.sealed.zeroize();
+    }
+}
+
+impl<T: Shieldable> fmt::Debug for ShieldedClassifiedData<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ShieldedClassifiedData(<REDACTED>)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_is_not_stored_in_plaintext() {
+        let secret = b"correct horse battery staple".to_vec();
+        let shielded = ShieldedClassifiedData::new(secret.clone());
+        assert!(!shielded
+            .sealed
+            .windows(secret.len())
+            .any(|w| w == secret.as_slice()));
+    }
+
+    #[test]
+    fn expose_round_trips() {
+        let shielded = ShieldedClassifiedData::new([7u8; 32]);
+        let copy = shielded.expose(|v| *v).unwrap();
+        assert_eq!(copy, [7u8; 32]);
+    }
+
+    #[test]
+    fn update_reshields_under_new_prekey() {
+        let mut shielded = ShieldedClassifiedData::new(vec![1u8, 2, 3]);
+        let before = shielded.prekey.expose_secret().clone();
+
+        shielded.update(|v| v.push(4)).unwrap();
+
+        assert_ne!(&before, shielded.prekey.expose_secret());
+        assert_eq!(shielded.expose(|v| v.clone()).unwrap(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn corrupted_ciphertext_is_rejected() {
+        let mut shielded = ShieldedClassifiedData::new(vec![9u8; 8]);
+        shielded.sealed[0] ^= 0x01;
+        assert!(matches!(shielded.expose(|_| ()), Err(CryptoError::DecryptionError)));
+    }
+
+    #[test]
+    fn unshield_returns_classified_data() {
+        let shielded = ShieldedClassifiedData::new(vec![5u8, 6]);
+        let plain = shielded.unshield().unwrap();
+        assert_eq!(plain.expose(), &[5, 6]);
+    }
+
+    thread_local! {
+        static WIPED: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    }
+
+    /// Records whether the unshielded copy was zeroized.
+    struct Tracked(Vec<u8>);
+
+    impl Zeroize for Tracked {
+        fn zeroize(&mut self) {
+            self.0.zeroize();
+            WIPED.with(|w| w.set(true));
+        }
+    }
+
+    impl Shieldable for Tracked {
+        fn shield_bytes(&self) -> &[u8] {
+            &self.0
+        }
+
+        fn from_shield_bytes(bytes: Vec<u8>) -> Self {
+            Tracked(bytes)
+        }
+    }
+
+    #[test]
+    fn plaintext_is_wiped_when_closure_panics() {
+        let mut shielded = ShieldedClassifiedData::new(Tracked(vec![1, 2, 3]));
+
+        WIPED.with(|w| w.set(false));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            shielded.expose(|_| panic!("boom")).unwrap();
+        }));
+        assert!(result.is_err());
+        assert!(WIPED.with(|w| w.get()));
+
+        WIPED.with(|w| w.set(false));
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            shielded.update(|_| panic!("boom")).unwrap();
+        }));
+        assert!(result.is_err());
+        assert!(WIPED.with(|w| w.get()));
+        assert_eq!(shielded.expose(|t| t.0.clone()).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn debug_is_redacted() {
+        let shielded = ShieldedClassifiedData::new(vec![1u8]);
+        assert_eq!(format!("{:?}", shielded), "ShieldedClassifiedData(<REDACTED>)");
+    }
+}
@@ -23,8 +23,17 @@
 //! 
 #![cfg(feature = "async")]
 
+use std::{mem::ManuallyDrop, ptr};
+
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
 use zeroize::Zeroize;
 
+use crate::{
+    crypto::{crypto_algorithm::CryptoAlgorithm, crypto_primitive::CryptoPrimitive},
+    declassification::Principal,
+    errors::{CryptoError, SecureError},
+};
+
 #[derive(Clone)]
 pub struct SizedClassifiedData<T: Zeroize + Sized> {
     data: T,
@@ -43,19 +52,42 @@ impl<T: Zeroize + Sized> SizedClassifiedData<T> {
         &mut self.data
     }
 
-    // pub fn into_data(mut self) -> T {
-    //     let taken = std::mem::take(&mut self.data);
-    //     self.zeroize();
-    //     taken
-    // }
+    /// Release the value as plain data on the authority of `authorizer`.
+    ///
+    /// This is the only way to take the value out of the wrapper; the
+    /// release is recorded through the [declassification sink](crate::declassification).
+    ///
+    /// # Panics
+    /// Panics if `reason` is blank.
+    #[track_caller]
+    pub fn declassify(self, reason: &str, authorizer: &Principal) -> T {
+        crate::declassification::audit("SizedClassifiedData", None, reason, authorizer);
+        self.into_data()
+    }
+
+    pub(crate) fn into_data(self) -> T {
+        // `Drop` must not run: it would zeroize the value being handed out,
+        // which for heap-backed `T` shares its buffer with the returned copy.
+        let this = ManuallyDrop::new(self);
+        // SAFETY: `this` is never used or dropped again, so the read moves
+        // `data` out exactly once.
+        unsafe { ptr::read(&this.data) }
+    }
+}
 
-    pub fn into_data(mut self) -> T {
-        use std::ptr;
+#[cfg(unix)]
+use crate::locked_classified_data::{GuardedClassifiedData, LockedClassifiedData};
+
+#[cfg(unix)]
+impl<T: Zeroize> SizedClassifiedData<T> {
+    /// Move the secret onto `mlock`ed pages.
+    pub fn into_locked(self) -> LockedClassifiedData<T> {
+        LockedClassifiedData::new(self.into_data())
+    }
 
-        let value = unsafe { ptr::read(&self.data) };
-        self.zeroize(); // optional: in case Drop still runs
-        std::mem::forget(self); // prevent Drop from double zeroizing
-        value
+    /// Move the secret onto locked pages fenced by guard pages and a canary.
+    pub fn into_guarded(self) -> GuardedClassifiedData<T> {
+        GuardedClassifiedData::new(self.into_data())
     }
 }
 
@@ -90,26 +122,91 @@ impl<T: Zeroize + Sized> DerefMut for SizedClassifiedData<T> {
 
 use crate::{impl_debug, impl_sized_drop};
 impl_debug!(SizedClassifiedData);
+crate::impl_ct_ops!(plain SizedClassifiedData);
+
+
+/// A fixed-size secret byte array, e.g. a 32-byte symmetric key.
+///
+/// # Example
+/// ```
+/// use classified::sized_classified_data::ClassifiedArray;
+/// use classified::crypto::crypto_algorithm::CryptoAlgorithm;
+///
+/// let key = ClassifiedArray::<32>::random();
+/// let primitive = key.into_primitive(&CryptoAlgorithm::AES).unwrap();
+/// assert_eq!(primitive.key_length.as_bytes(), 32);
+///
+/// assert!(ClassifiedArray::<32>::from_slice(&[0u8; 16]).is_err());
+/// ```
+pub type ClassifiedArray<const N: usize> = SizedClassifiedData<[u8; N]>;
+
+impl<const N: usize> SizedClassifiedData<[u8; N]> {
+    /// Copy `bytes` into a new array, rejecting any length other than `N`.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, SecureError> {
+        let data: [u8; N] = bytes.try_into().map_err(|_| SecureError::InvalidLength {
+            expected: N,
+            actual: bytes.len(),
+        })?;
+        Ok(Self::new(data))
+    }
 
+    /// Fill a new array from the operating system's CSPRNG.
+    pub fn random() -> Self {
+        let mut secret = Self::new([0u8; N]);
+        getrandom::getrandom(&mut secret.data).expect("failed to draw random bytes from the OS");
+        secret
+    }
 
-// impl AsRef<[u8]> for SizedClassifiedData<[u8]> {
-//     fn as_ref(&self) -> &[u8] {
-//         &self.data
-//     }
-// }
+    /// Constant-time choice between `a` (when `choice` is 0) and `b`.
+    ///
+    /// The result is built in place inside the new wrapper, so no bare copy
+    /// of either input is left on the stack.
+    pub fn ct_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mut selected = Self::new([0u8; N]);
+        for ((byte, x), y) in selected.data.iter_mut().zip(a.data.iter()).zip(b.data.iter()) {
+            *byte = u8::conditional_select(x, y, choice);
+        }
+        selected
+    }
 
-// impl AsRef<[u8]> for SizedClassifiedData<[u8; 32]> {
-//     fn as_ref(&self) -> &[u8] {
-//         &self.data[..]
-//     }
-// }
+    /// Overwrite `self` with `other` if `choice` is 1, in constant time.
+    pub fn ct_assign(&mut self, other: &Self, choice: Choice) {
+        for (byte, new) in self.data.iter_mut().zip(other.data.iter()) {
+            byte.conditional_assign(new, choice);
+        }
+    }
+
+    /// Turn the array into the key of a [`CryptoPrimitive`] for `algorithm`.
+    ///
+    /// Fails if `N` does not match the key length of `algorithm`. The bytes
+    /// are copied once, straight into the primitive's key buffer, and the
+    /// array is zeroized immediately afterwards.
+    pub fn into_primitive(mut self, algorithm: &CryptoAlgorithm) -> Result<CryptoPrimitive, CryptoError> {
+        let mut key = Vec::with_capacity(N);
+        key.extend_from_slice(&self.data);
+        self.data.zeroize();
+        CryptoPrimitive::new(algorithm, key, true)
+    }
+}
 
-impl AsRef<[u8; 32]> for SizedClassifiedData<[u8; 32]> {
-    fn as_ref(&self) -> &[u8; 32] {
+impl<const N: usize> AsRef<[u8; N]> for SizedClassifiedData<[u8; N]> {
+    fn as_ref(&self) -> &[u8; N] {
         &self.data
     }
 }
 
+impl<const N: usize> AsRef<[u8]> for SizedClassifiedData<[u8; N]> {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl<const N: usize> ConstantTimeEq for SizedClassifiedData<[u8; N]> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.data.ct_eq(&other.data)
+    }
+}
+
 impl AsRef<[u8]> for SizedClassifiedData<Vec<u8>> {
     fn as_ref(&self) -> &[u8] {
         &self.data
@@ -154,6 +251,20 @@ mod tests {
         assert_eq!(result, data);
     }
 
+    #[test]
+    fn test_declassify_releases_value() {
+        let sink = crate::declassification::tests::recording_sink();
+        let sensitive = SizedClassifiedData::new([3u8; 32]);
+
+        let public = sensitive.declassify("sized release test", &Principal::new("bob"));
+        assert_eq!(public, [3u8; 32]);
+
+        let records = sink.0.lock().unwrap();
+        let record = records.iter().find(|r| r.reason == "sized release test").unwrap();
+        assert_eq!(record.source, "SizedClassifiedData");
+        assert_eq!(record.principal, "bob");
+    }
+
     #[test]
     fn test_zeroize_on_drop() {
         use std::cell::RefCell;
@@ -177,6 +288,14 @@ mod tests {
         assert_eq!(*Rc::new(RefCell::new(vec![0u8; 3])).borrow(), vec![0u8, 0, 0]);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_into_guarded_moves_value() {
+        let sensitive = SizedClassifiedData::new([8u8; 32]);
+        let guarded = sensitive.into_guarded();
+        assert_eq!(guarded.expose(), &[8u8; 32]);
+    }
+
     #[test]
     fn test_debug_does_not_leak() {
         let sensitive = SizedClassifiedData::new([1u8; 32]);
@@ -188,7 +307,57 @@ mod tests {
     fn test_as_ref_for_array_32() {
         let data = [9u8; 32];
         let sensitive = SizedClassifiedData::new(data);
-        assert_eq!(sensitive.as_ref(), &data);
+        assert_eq!(AsRef::<[u8; 32]>::as_ref(&sensitive), &data);
+        assert_eq!(AsRef::<[u8]>::as_ref(&sensitive), &data[..]);
+    }
+
+    #[test]
+    fn test_into_data_keeps_heap_contents() {
+        let sensitive = SizedClassifiedData::new(vec![1u8, 2, 3]);
+        assert_eq!(sensitive.into_data(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_array_from_slice_checks_length() {
+        let key = ClassifiedArray::<16>::from_slice(&[5u8; 16]).unwrap();
+        assert_eq!(key.expose(), &[5u8; 16]);
+
+        assert!(matches!(
+            ClassifiedArray::<16>::from_slice(&[5u8; 15]),
+            Err(SecureError::InvalidLength { expected: 16, actual: 15 })
+        ));
+    }
+
+    #[test]
+    fn test_array_random_and_ct_eq() {
+        let a = ClassifiedArray::<32>::random();
+        let b = ClassifiedArray::<32>::random();
+        let a_copy = ClassifiedArray::<32>::from_slice(a.as_ref()).unwrap();
+
+        assert!(bool::from(a.ct_eq(&a_copy)));
+        assert!(!bool::from(a.ct_eq(&b)));
+    }
+
+    #[test]
+    fn test_array_ct_select() {
+        let a = ClassifiedArray::<4>::new([1; 4]);
+        let b = ClassifiedArray::<4>::new([2; 4]);
+
+        assert_eq!(ClassifiedArray::ct_select(&a, &b, Choice::from(0)).expose(), &[1; 4]);
+        assert_eq!(ClassifiedArray::ct_select(&a, &b, Choice::from(1)).expose(), &[2; 4]);
+    }
+
+    #[test]
+    fn test_array_into_primitive() {
+        let primitive = ClassifiedArray::<32>::new([7; 32])
+            .into_primitive(&CryptoAlgorithm::ChaCha20Poly1305)
+            .unwrap();
+        assert!(matches!(primitive.algorithm, CryptoAlgorithm::ChaCha20Poly1305));
+
+        let primitive = ClassifiedArray::<32>::new([7; 32]).into_primitive(&CryptoAlgorithm::AES).unwrap();
+        assert!(matches!(primitive.algorithm, CryptoAlgorithm::AES));
+
+        assert!(ClassifiedArray::<16>::random().into_primitive(&CryptoAlgorithm::AES).is_err());
     }
 
     #[test]
@@ -197,4 +366,17 @@ mod tests {
         let sensitive = SizedClassifiedData::new(data.clone());
         assert_eq!(sensitive.as_ref(), data.as_slice());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_constant_time_ops_check_array_length() {
+        use crate::{constant_time::ConstantTimeOps, errors::EncodingError};
+
+        let key = ClassifiedArray::<4>::from_hex("00ff10a0").unwrap();
+        assert_eq!(key.expose(), &[0x00, 0xff, 0x10, 0xa0]);
+        assert_eq!(key.to_base64().expose(), "AP8QoA==");
+        assert_eq!(ClassifiedArray::<4>::from_hex("00ff").err(), Some(EncodingError::InvalidLength(2)));
+
+        let picked = ClassifiedArray::ct_conditional_select(&key, &ClassifiedArray::new([0; 4]), Choice::from(1));
+        assert_eq!(picked.expose(), &[0; 4]);
+    }
+}
@@ -28,13 +28,31 @@ pub trait ClassifiedEq<Rhs = Self> {
     fn classified_eq(&self, rhs: &Rhs) -> bool;
 }
 
+/// Async counterpart of [`ClassifiedEq`] for wrappers whose value sits behind
+/// an async lock.
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+pub trait AsyncClassifiedEq<Rhs: Sync = Self>: Sync {
+    async fn classified_eq_async(&self, rhs: &Rhs) -> bool;
+}
+
+/// A wrapper that can hold a classified value of type `V`.
+///
+/// Lets containers such as [`ClassifiedMap`](crate::classified_map::ClassifiedMap)
+/// choose how their values are protected at rest.
+pub trait ClassifiedStorage<V: zeroize::Zeroize> {
+    fn classify(value: V) -> Self;
+}
+
 #[allow(unused)]trait KeyType:Eq+std::hash::Hash+Clone+std::fmt::Debug{}
 #[allow(unused)]trait ValueType:zeroize::Zeroize+Clone+std::fmt::Debug{}
 
 
 use crate::{
+    crypto::signature::Signature,
     errors::SecureError,
-    classified_data::ClassifiedData
+    classified_data::ClassifiedData,
+    classification::{Below, Label, Level, Secret},
 };
 
 pub trait LayerHandler: Send + Sync {
@@ -44,10 +62,47 @@ pub trait LayerHandler: Send + Sync {
     ) -> Result<ClassifiedData<Vec<u8>>, SecureError>;
 }
 
+/// Non-secret information travelling with a [`Frame`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FrameMetadata {
+    /// Security label of the frame, checked when a
+    /// [`LayerStack`](crate::layer_stack::LayerStack) forwards it.
+    pub label: Label,
+    pub note: Option<String>,
+    /// Signatures over `data`, appended by a
+    /// [`SigningStage`](crate::crypto::signing_stage::SigningStage).
+    pub signatures: Vec<Signature>,
+}
+
+/// A unit of classified data passed between layers, labelled with level `L`.
 #[derive(Clone, Debug)]
-pub struct Frame {
-    pub data: ClassifiedData<Vec<u8>>,
-    pub metadata: Option<String>,
+pub struct Frame<L: Level = Secret> {
+    pub data: ClassifiedData<Vec<u8>, L>,
+    pub metadata: FrameMetadata,
+}
+
+impl<L: Level> Frame<L> {
+    /// Wrap `data`, copying its label into the metadata.
+    pub fn new(data: ClassifiedData<Vec<u8>, L>) -> Self {
+        let label = data.label();
+        Self {
+            data,
+            metadata: FrameMetadata { label, note: None, signatures: Vec::new() },
+        }
+    }
+
+    /// Move the frame to a more sensitive level.
+    pub fn upgrade<H: Level>(self) -> Frame<H>
+    where
+        L: Below<H>,
+    {
+        let mut metadata = self.metadata;
+        metadata.label.level = metadata.label.level.max(H::CLASSIFICATION);
+        Frame {
+            data: self.data.upgrade(),
+            metadata,
+        }
+    }
 }
 
 
@@ -56,18 +111,18 @@ use async_trait::async_trait;
 
 #[cfg(feature = "async")]
 #[async_trait]
-pub trait PipelineStage: 'static + Send + Sync {
+pub trait PipelineStage<L: Level = Secret>: 'static + Send + Sync {
     async fn process(
         &self,
-        data: ClassifiedData<Vec<u8>>,
-    ) -> Result<ClassifiedData<Vec<u8>>, SecureError>;
+        data: ClassifiedData<Vec<u8>, L>,
+    ) -> Result<ClassifiedData<Vec<u8>, L>, SecureError>;
 }
 
 #[cfg(feature = "async")]
 #[async_trait::async_trait]
-pub trait FramedLayerHandler: Send + Sync {
+pub trait FramedLayerHandler<L: Level = Secret>: Send + Sync {
     async fn handle(
         &self,
-        frame: Frame
-    ) -> Result<Frame, SecureError>;
+        frame: Frame<L>
+    ) -> Result<Frame<L>, SecureError>;
 }
@@ -20,8 +20,37 @@
 
 
 
+#[cfg(feature = "std")]
+use core::{
+    mem::{self, ManuallyDrop, MaybeUninit},
+    ptr, slice,
+};
+
+#[cfg(feature = "std")]
+use secrecy::{ExposeSecretMut, SecretBox};
 use zeroize::Zeroize;
 
+/// Moves the value out of a [`SecretBox`] without requiring `T: Default`.
+///
+/// The heap slot the value leaves behind is wiped before it is freed, so the
+/// only remaining copy of the secret is the one returned.
+#[cfg(feature = "std")]
+pub(crate) fn unbox_secret<T: Zeroize>(secret: SecretBox<T>) -> T {
+    // Never move `secret` again once `raw` is derived from it.
+    let mut secret = ManuallyDrop::new(secret);
+    let raw: *mut T = secret.expose_secret_mut();
+    // SAFETY: `raw` points at the initialized `T` owned by the forgotten
+    // box, which is read exactly once. The allocation was made by `Box<T>`,
+    // so it is freed with the same layout as `MaybeUninit<T>`, which does
+    // not drop the value that was moved out.
+    unsafe {
+        let value = ptr::read(raw);
+        let mut slot = Box::from_raw(raw.cast::<MaybeUninit<T>>());
+        slice::from_raw_parts_mut(slot.as_mut_ptr().cast::<u8>(), mem::size_of::<T>()).zeroize();
+        value
+    }
+}
+
 pub struct ZeroizingGuard<'a, T: Zeroize> {
     pub data: &'a mut T,
     active: bool,
@@ -44,3 +73,25 @@ impl<'a, T: Zeroize> Drop for ZeroizingGuard<'a, T> {
         }
     }
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unbox_secret_moves_heap_backed_values() {
+        let secret = SecretBox::new(Box::new(vec![1u8, 2, 3]));
+        let ptr = secrecy::ExposeSecret::expose_secret(&secret).as_ptr();
+
+        let value = unbox_secret(secret);
+        assert_eq!(value, [1, 2, 3]);
+        assert_eq!(value.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn unbox_secret_handles_non_default_arrays() {
+        let value = unbox_secret(SecretBox::new(Box::new([7u8; 64])));
+        assert_eq!(value, [7u8; 64]);
+        unbox_secret(SecretBox::new(Box::new(())));
+    }
+}